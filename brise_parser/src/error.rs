@@ -1,12 +1,68 @@
-use brise_token::BriseFile;
+use std::fmt::Display;
+
+use brise_token::{BriseFile, SourceMap, Token};
 use thiserror::Error;
 
-use crate::tokens::error::ParsingErrors;
+use crate::{expr::error::ExprError, stmt::error::StmtError, tokens::error::ParsingErrors};
 
 #[derive(Debug, Error)]
 pub enum ParserError {
     #[error(transparent)]
     Parsing(#[from] ParsingErrors),
+    #[error(transparent)]
+    Expr(#[from] ExprError),
+    #[error(transparent)]
+    Stmt(#[from] StmtError),
     #[error("Failed to read file: {} - {}", .0.as_path().display(), .1)]
     FailedToReadFile(BriseFile, std::io::Error),
 }
+
+impl ParserError {
+    /// Start of the first error, to report the errors of a parse in the order of the input
+    pub(crate) fn start(&self) -> usize {
+        match self {
+            Self::Parsing(errors) => errors
+                .errors()
+                .first()
+                .map_or(0, |error| error.context().span().start()),
+            Self::Expr(error) => error.context().span().start(),
+            Self::Stmt(error) => error.context().span().start(),
+            Self::FailedToReadFile(..) => 0,
+        }
+    }
+
+    /// Error message along with the source lines where the errors happened
+    pub fn render(&self, source_map: &SourceMap) -> String {
+        match self {
+            Self::Parsing(errors) => errors.render(source_map),
+            Self::Expr(error) => error.render(source_map),
+            Self::Stmt(error) => error.render(source_map),
+            Self::FailedToReadFile(..) => self.to_string(),
+        }
+    }
+}
+
+/// What the parser met instead of what it expected
+#[derive(Debug, PartialEq)]
+pub enum Found {
+    /// Text of the token
+    Token(String),
+    EndOfInput,
+}
+
+impl From<Option<&Token>> for Found {
+    fn from(value: Option<&Token>) -> Self {
+        value.map_or(Self::EndOfInput, |token| {
+            Self::Token(token.variant().to_string())
+        })
+    }
+}
+
+impl Display for Found {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Token(text) => write!(f, "`{text}`"),
+            Self::EndOfInput => f.write_str("the end of the input"),
+        }
+    }
+}
@@ -1,225 +1,451 @@
 use std::collections::VecDeque;
 
 use brise_syntax_tree::expr::{
-    binary::{BinaryExpr, BinaryOperator, BinaryOperatorVariant},
+    binary::{BinaryExpr, BinaryOperator},
+    call::CallExpr,
+    error::ErrorExpr,
+    field::FieldExpr,
+    grouping::GroupingExpr,
     identifier::Identifier,
+    index::IndexExpr,
     literal::{Literal, LiteralVariant, NumberLiteral},
     unary::UnaryExpr,
-    Expr, ExprVariant,
+    Expr,
 };
-use brise_token::{BriseContext, Token, TokenVariant};
+use brise_token::{BriseContext, RawString, Token, TokenKind, TokenSet, TokenVariant};
 use error::{ExprError, ExprErrorVariant};
+use precedence::Associativity;
+pub(crate) use precedence::OPERATOR_KINDS;
+
+use crate::{
+    error::Found,
+    state::{ParserState, SyntaxError},
+};
 
 pub mod error;
+mod precedence;
 #[cfg(test)]
 mod tests;
 
+/// Kinds of the tokens starting an expression
+pub(crate) const EXPR_START: TokenSet = TokenSet::new(&[
+    TokenKind::LeftParen,
+    TokenKind::Minus,
+    TokenKind::Bang,
+    TokenKind::Identifier,
+    TokenKind::String,
+    TokenKind::FormattedString,
+    TokenKind::Integer,
+    TokenKind::Float,
+    TokenKind::True,
+    TokenKind::False,
+    TokenKind::QuestionMark,
+]);
+
+/// Kinds of the tokens continuing a postfix chain
+pub(crate) const POSTFIX_KINDS: TokenSet =
+    TokenSet::new(&[TokenKind::LeftParen, TokenKind::Dot, TokenKind::LeftBracket]);
+
+/// Parses an expression from the front of the tokens, the tokens after it are left
 #[derive(Debug)]
 pub struct ExprParser<'a> {
-    input: &'a mut VecDeque<Token>,
-    last_context: BriseContext,
+    /// `recovering` is cleared once the next token is consumed
+    state: ParserState<'a, ExprError>,
 }
 
 impl<'a> ExprParser<'a> {
+    /// Stops at the first error, see [`ExprParser::parse_partial`] to get every error
     pub fn parse(tokens: &'a mut VecDeque<Token>) -> Result<Expr, ExprError> {
-        Self::new(tokens).parse_input()
+        let (expr, errors) = Self::parse_partial(tokens);
+
+        match errors.into_iter().next() {
+            Some(error) => Err(error),
+            None => Ok(expr),
+        }
+    }
+
+    /// Input that can't be parsed becomes `ExprVariant::Error` nodes, every error
+    /// is returned along with the expression
+    pub fn parse_partial(tokens: &'a mut VecDeque<Token>) -> (Expr, Vec<ExprError>) {
+        let mut parser = Self::new(tokens, BriseContext::default());
+        let expr = parser.parse_input();
+
+        (expr, parser.state.errors)
+    }
+
+    /// Parses an expression out of the tokens of another parser, which goes on with
+    /// the kinds that could have continued the expression. The errors of the expression
+    /// are reported to it, unless it is already recovering, and it is left recovering
+    /// when the expression ends in an error.
+    pub(crate) fn parse_after<E: SyntaxError + From<ExprError>>(
+        outer: &mut ParserState<'_, E>,
+    ) -> Expr {
+        let mut parser = ExprParser::new(outer.input, outer.last_context.clone());
+        parser.state.expected = outer.expected;
+        let expr = parser.parse_input();
+        outer.last_context = parser.state.last_context;
+        outer.expected = parser.state.expected;
+
+        let failed = !parser.state.errors.is_empty() || parser.state.recovering;
+        if !outer.recovering {
+            outer
+                .errors
+                .extend(parser.state.errors.into_iter().map(E::from));
+        }
+        outer.recovering |= failed;
+
+        expr
     }
 
-    fn new(input: &'a mut VecDeque<Token>) -> Self {
+    fn new(input: &'a mut VecDeque<Token>, last_context: BriseContext) -> Self {
         Self {
-            input,
-            last_context: BriseContext::default(),
+            state: ParserState::new(input, last_context),
         }
     }
 
-    fn parse_input(&mut self) -> Result<Expr, ExprError> {
-        self.parse_or()
+    fn parse_input(&mut self) -> Expr {
+        self.parse_binary(0)
     }
 
-    fn parse_or(&mut self) -> Result<Expr, ExprError> {
-        let expr = self.parse_and()?;
+    /// Parses operators binding at least as tight as `min_power`, following
+    /// the table of [`precedence::OPERATORS`]
+    fn parse_binary(&mut self, min_power: u8) -> Expr {
+        let mut expr = self.parse_unary();
+        // Power of the last non associative operator, it can't be followed by its own level
+        let mut closed_power = None;
+        let mut chain_reported = false;
+
+        loop {
+            let Some(operator) = self
+                .state
+                .input
+                .front()
+                .and_then(|token| precedence::binary_operator(token.kind()))
+            else {
+                self.state.expected = self.state.expected.union(OPERATOR_KINDS);
+                break;
+            };
+            if operator.power < min_power {
+                break;
+            }
 
-        if self
-            .input
-            .front()
-            .is_some_and(|token| matches!(token.variant(), TokenVariant::BarBar))
-        {
-            let operator_token = self.input.pop_front().unwrap();
-            let right = self.parse_or()?;
-            let binary_operator =
-                BinaryOperator::new(BinaryOperatorVariant::Or, operator_token.into());
-            let binary_expr = BinaryExpr::new(expr, binary_operator, right);
+            let operator_token = self.pop_front().unwrap();
+            let right = self.parse_binary(operator.right_power());
+            // Only ranges are non associative, the chain is dropped as a whole
+            if closed_power == Some(operator.power) {
+                if !chain_reported {
+                    self.error(ExprErrorVariant::ChainedRange, operator_token.into());
+                    chain_reported = true;
+                }
+                expr = ErrorExpr::new(expr.context().to(&right.context())).into();
+                continue;
+            }
+            let binary_operator = BinaryOperator::new(operator.variant, operator_token.into());
+            expr = BinaryExpr::new(expr, binary_operator, right).into();
 
-            return Ok(binary_expr.into());
+            if operator.associativity == Associativity::None {
+                closed_power = Some(operator.power);
+            }
         }
 
-        Ok(expr)
+        expr
     }
 
-    fn parse_and(&mut self) -> Result<Expr, ExprError> {
-        let expr = self.parse_equality()?;
-
+    fn parse_unary(&mut self) -> Expr {
         if self
+            .state
             .input
             .front()
-            .is_some_and(|token| matches!(token.variant(), TokenVariant::AmpersandAmpersand))
+            .is_some_and(|token| token.is_unary())
         {
-            let operator_token = self.input.pop_front().unwrap();
-            let right = self.parse_and()?;
-            let binary_operator =
-                BinaryOperator::new(BinaryOperatorVariant::And, operator_token.into());
-            let binary_expr = BinaryExpr::new(expr, binary_operator, right);
+            let operator_token = self.pop_front().unwrap();
+            let right = self.parse_binary(precedence::UNARY_POWER);
+            let unary_operator = operator_token.try_into().unwrap();
+            let unary_expr = UnaryExpr::new(unary_operator, right);
 
-            return Ok(binary_expr.into());
+            return unary_expr.into();
         }
 
-        Ok(expr)
+        self.parse_call()
     }
 
-    fn parse_equality(&mut self) -> Result<Expr, ExprError> {
-        let mut expr = self.parse_comparison()?;
-
-        while self.input.front().is_some_and(|token| token.is_equality()) {
-            let operator_token = self.input.pop_front().unwrap();
-            let right = self.parse_comparison()?;
-            let binary_operator = operator_token.try_into().unwrap();
-            let binary_expr = BinaryExpr::new(expr, binary_operator, right);
-
-            expr = binary_expr.into();
+    /// Postfix chains like `a.b(c)[d](e)`, applied from left to right
+    fn parse_call(&mut self) -> Expr {
+        let mut expr = self.parse_primary();
+        // The token which isn't an expression is left, it doesn't start a postfix
+        if self.state.recovering {
+            return expr;
         }
 
-        Ok(expr)
+        loop {
+            expr = match self.state.input.front().map(Token::variant) {
+                Some(TokenVariant::LeftParen) => self.parse_arguments(expr),
+                Some(TokenVariant::Dot) => self.parse_field(expr),
+                Some(TokenVariant::LeftBracket) => self.parse_index(expr),
+                _ => {
+                    self.state.expected = self.state.expected.union(POSTFIX_KINDS);
+                    return expr;
+                }
+            };
+        }
     }
 
-    fn parse_comparison(&mut self) -> Result<Expr, ExprError> {
-        let mut expr = self.parse_term()?;
+    /// `(a, b)` after the callee, a trailing comma is an error
+    fn parse_arguments(&mut self, callee: Expr) -> Expr {
+        let left_paren = self.pop_front().unwrap();
+        let mut args = vec![];
 
-        while self
-            .input
-            .front()
-            .is_some_and(|token| token.is_comparison())
-        {
-            let operator_token = self.input.pop_front().unwrap();
-            let right = self.parse_term()?;
-            let binary_operator = operator_token.try_into().unwrap();
-            let binary_expr = BinaryExpr::new(expr, binary_operator, right);
+        loop {
+            if args.is_empty() && self.state.next_is(TokenKind::RightParen) {
+                self.pop_front();
+                break;
+            }
+            args.push(self.parse_input());
 
-            expr = binary_expr.into();
+            // The rest of a failed argument isn't checked, like a comma before `)`
+            if self.state.recovering {
+                self.skip_to_closing(TokenKind::RightParen);
+                break;
+            }
+            if self.state.next_is(TokenKind::RightParen) {
+                self.pop_front();
+                break;
+            }
+            if self.state.next_is(TokenKind::Comma) {
+                let comma = self.pop_front().unwrap();
+
+                if self.state.next_is(TokenKind::RightParen) {
+                    self.error(ExprErrorVariant::TrailingComma, comma.into());
+                    self.pop_front();
+                    break;
+                }
+                continue;
+            }
+
+            if self.state.input.is_empty() {
+                self.error(ExprErrorVariant::UnclosedCall, left_paren.context().clone());
+            } else {
+                self.state.unexpected();
+                self.skip_to_closing(TokenKind::RightParen);
+            }
+            break;
         }
+        let context = left_paren.context().to(&self.state.last_context);
 
-        Ok(expr)
+        CallExpr::new(callee, args, context).into()
     }
 
-    fn parse_term(&mut self) -> Result<Expr, ExprError> {
-        let mut expr = self.parse_factor()?;
+    fn parse_field(&mut self, object: Expr) -> Expr {
+        let dot = self.pop_front().unwrap();
 
-        while self.input.front().is_some_and(|token| token.is_term()) {
-            let operator_token = self.input.pop_front().unwrap();
-            let right = self.parse_factor()?;
-            let binary_operator = operator_token.try_into().unwrap();
-            let binary_expr = BinaryExpr::new(expr, binary_operator, right);
+        if self.state.next_is(TokenKind::Identifier) {
+            let (variant, context) = self.pop_front().unwrap().into_parts();
+            let TokenVariant::Identifier(identity) = variant else {
+                unreachable!()
+            };
 
-            expr = binary_expr.into();
+            return FieldExpr::new(object, Identifier::new(identity, context)).into();
         }
 
-        Ok(expr)
+        self.state.unexpected();
+        ErrorExpr::new(object.context().to(dot.context())).into()
     }
 
-    fn parse_factor(&mut self) -> Result<Expr, ExprError> {
-        let mut expr = self.parse_unary()?;
+    fn parse_index(&mut self, object: Expr) -> Expr {
+        let left_bracket = self.pop_front().unwrap();
+        let index = self.parse_input();
+        self.closing(
+            &left_bracket,
+            TokenKind::RightBracket,
+            ExprErrorVariant::UnclosedIndex,
+        );
+        let context = left_bracket.context().to(&self.state.last_context);
+
+        IndexExpr::new(object, index, context).into()
+    }
 
-        if self.input.front().is_some_and(|token| token.is_factor()) {
-            let operator_token = self.input.pop_front().unwrap();
-            let right = self.parse_unary()?;
-            let binary_operator = operator_token.try_into().unwrap();
-            let binary_expr = BinaryExpr::new(expr, binary_operator, right);
+    /// A token which can't start an expression is left for the caller to recover from
+    fn parse_primary(&mut self) -> Expr {
+        let Some(token) = self.state.input.front() else {
+            self.expected_expression();
+            return ErrorExpr::new(self.state.last_context.clone()).into();
+        };
+
+        match token.variant() {
+            TokenVariant::LeftParen => {
+                let left_paren = self.pop_front().unwrap();
+                let expr = self.parse_input();
+                self.closing(
+                    &left_paren,
+                    TokenKind::RightParen,
+                    ExprErrorVariant::UnclosedGrouping,
+                );
+                let context = left_paren.context().to(&self.state.last_context);
+
+                GroupingExpr::new(expr, context).into()
+            }
+            TokenVariant::Identifier(identity) => {
+                let identity = *identity;
+                let token = self.pop_front().unwrap();
+
+                Identifier::new(identity, token.into()).into()
+            }
+            variant if variant.is_literal() => {
+                let token = self.pop_front().unwrap();
+                self.parse_literal(token)
+            }
+            // The lexer has already reported the error
+            TokenVariant::Error => {
+                let token = self.pop_front().unwrap();
+                ErrorExpr::new(token.into()).into()
+            }
+            _ => {
+                let context = token.context().clone();
+                self.expected_expression();
 
-            expr = binary_expr.into();
+                ErrorExpr::new(context).into()
+            }
         }
+    }
 
-        Ok(expr)
+    fn parse_literal(&mut self, token: Token) -> Expr {
+        let (variant, context) = token.into_parts();
+        let literal = match variant {
+            TokenVariant::Integer(value) => Literal::new(LiteralVariant::Integer(value), context),
+            TokenVariant::Float(value) => {
+                let number = NumberLiteral::new(value);
+                Literal::new(LiteralVariant::Float(number), context)
+            }
+            TokenVariant::String(value) => Literal::new(LiteralVariant::String(value), context),
+            TokenVariant::FormattedString(parts) => {
+                let exprs = self.parse_formatted_string(parts, &context);
+                Literal::new(LiteralVariant::FormattedString(exprs), context)
+            }
+            TokenVariant::True => Literal::new(LiteralVariant::True, context),
+            TokenVariant::False => Literal::new(LiteralVariant::False, context),
+            TokenVariant::QuestionMark => Literal::new(LiteralVariant::Unknown, context),
+            _ => unreachable!(),
+        };
+
+        literal.into()
     }
 
-    fn parse_unary(&mut self) -> Result<Expr, ExprError> {
-        if self.input.front().is_some_and(|token| token.is_unary()) {
-            let operator_token = self.input.pop_front().unwrap();
-            let right = self.parse_unary()?;
-            let unary_operator = operator_token.try_into().unwrap();
-            let unary_expr = UnaryExpr::new(unary_operator, right);
+    /// Every text segment becomes a string literal, followed by the expression
+    /// of its interpolation
+    fn parse_formatted_string(
+        &mut self,
+        parts: Vec<(RawString, Vec<Token>)>,
+        context: &BriseContext,
+    ) -> Vec<Expr> {
+        let mut exprs = vec![];
+
+        for (text, tokens) in parts {
+            if !text.as_str().is_empty() {
+                let literal = Literal::new(LiteralVariant::String(text), context.clone());
+                exprs.push(literal.into());
+            }
+
+            if tokens.is_empty() {
+                continue;
+            }
 
-            return Ok(unary_expr.into());
+            let mut tokens = VecDeque::from(tokens);
+            let mut parser = ExprParser::new(&mut tokens, context.clone());
+            exprs.push(parser.parse_input());
+
+            if parser.state.errors.is_empty() && !parser.state.input.is_empty() {
+                parser.state.expected.insert(TokenKind::RightBrace);
+                parser.state.unexpected();
+            }
+            self.state.errors.append(&mut parser.state.errors);
         }
 
-        self.parse_call()
+        exprs
     }
 
-    fn parse_call(&mut self) -> Result<Expr, ExprError> {
-        self.parse_primary()
+    /// Consumes the token closing a delimited expression. When another token is met,
+    /// the input is skipped up to the closing token.
+    fn closing(&mut self, opening: &Token, closing: TokenKind, unclosed: ExprErrorVariant) {
+        if self.state.next_is(closing) {
+            self.pop_front();
+        } else if self.state.input.is_empty() {
+            self.error(unclosed, opening.context().clone());
+        } else {
+            self.state.unexpected();
+            self.skip_to_closing(closing);
+        }
     }
 
-    fn parse_primary(&mut self) -> Result<Expr, ExprError> {
-        if let Some(token) = self.input.pop_front() {
-            let token_variant = token.variant();
-            if let TokenVariant::LeftParen = token_variant {
-                let expr = self.parse_input()?;
+    /// Skips the rest of a delimited expression after an error, up to and including its
+    /// closing token. Without one before the end of the enclosing block, stops before
+    /// the next `;` instead.
+    fn skip_to_closing(&mut self, closing: TokenKind) {
+        if let Some(index) = self.closing_index(closing) {
+            for _ in 0..=index {
+                self.pop_front();
+            }
+            return;
+        }
+
+        while self.state.input.front().is_some_and(|token| {
+            !matches!(
+                token.variant(),
+                TokenVariant::Semicolon | TokenVariant::RightBrace
+            )
+        }) {
+            self.pop_front();
+        }
+    }
 
-                if self
-                    .input
-                    .front()
-                    .is_some_and(|token| matches!(token.variant(), TokenVariant::RightParen))
-                {
-                    self.input.pop_front();
+    /// Index of the unmatched `closing` token, before a `}` closing an outer block
+    fn closing_index(&self, closing: TokenKind) -> Option<usize> {
+        let mut depth = 0usize;
 
-                    let expr = ExprVariant::Grouping(expr);
-                    return Ok(Expr::new(expr));
-                } else {
-                    return Err(ExprError::new(
-                        ExprErrorVariant::UnclosedGrouping,
-                        token.into(),
-                    ));
+        for (index, token) in self.state.input.iter().enumerate() {
+            match token.variant() {
+                variant if depth == 0 && variant.kind() == closing => return Some(index),
+                TokenVariant::RightBrace if depth == 0 => return None,
+                TokenVariant::LeftParen | TokenVariant::LeftBracket | TokenVariant::LeftBrace => {
+                    depth += 1
                 }
-            } else if let TokenVariant::Identifier(identity) = token_variant {
-                let expr = ExprVariant::Identifier(Identifier::new(identity.clone(), token.into()));
-                return Ok(Expr::new(expr));
-            } else if token_variant.is_literal() {
-                return self.parse_literal(token);
+                TokenVariant::RightParen
+                | TokenVariant::RightBracket
+                | TokenVariant::RightBrace => depth = depth.saturating_sub(1),
+                _ => {}
             }
         }
 
-        Err(ExprError::new(
-            ExprErrorVariant::UnclosedGrouping,
-            self.last_context.clone(),
-        ))
+        None
     }
 
-    fn parse_literal(&mut self, token: Token) -> Result<Expr, ExprError> {
-        let literal = match token.variant() {
-            TokenVariant::Number(value) => {
-                let number = NumberLiteral::new(*value);
-                Literal::new(LiteralVariant::Number(number), token.into())
-            }
-            TokenVariant::String(value) => {
-                Literal::new(LiteralVariant::String(value.clone()), token.into())
-            }
-            TokenVariant::FormattedString(_) => {
-                unimplemented!()
-            }
-            TokenVariant::True => Literal::new(LiteralVariant::True, token.into()),
-            TokenVariant::False => Literal::new(LiteralVariant::False, token.into()),
-            TokenVariant::QuestionMark => Literal::new(LiteralVariant::Unknown, token.into()),
-            _ => unreachable!(),
-        };
+    /// Like [`ParserState::unexpected`], along with the kinds starting an expression
+    fn expected_expression(&mut self) {
+        let expected = self.state.expected.union(EXPR_START);
+        let (found, context) = self.state.found();
 
-        Ok(literal.into())
+        self.error(
+            ExprErrorVariant::ExpectedExpression { expected, found },
+            context,
+        );
     }
 
-    fn pop_front(&mut self) -> Token {
-        let token = self.input.pop_front().unwrap();
+    /// Only the first error at a token is reported, the others follow from it
+    fn error(&mut self, variant: ExprErrorVariant, context: BriseContext) {
+        self.state.error(ExprError::new(variant, context));
+    }
 
-        if self.input.is_empty() {
-            self.last_context = token.context().clone();
-        }
+    /// Errors are reported again once a token is consumed, unless the lexer already
+    /// reported this one
+    fn pop_front(&mut self) -> Option<Token> {
+        let token = self.state.pop_front()?;
+        self.state.recovering = token.variant() == &TokenVariant::Error;
+
+        Some(token)
+    }
+}
 
-        token
+impl SyntaxError for ExprError {
+    fn expected(expected: TokenSet, found: Found, context: BriseContext) -> Self {
+        Self::new(ExprErrorVariant::Expected { expected, found }, context)
     }
 }
@@ -1,12 +1,39 @@
-use brise_token::BriseContext;
+use brise_token::{BriseContext, SourceMap, TokenSet};
 use thiserror::Error;
 
-#[derive(Debug, Error)]
+use super::EXPR_START;
+use crate::error::Found;
+
+#[derive(Debug, PartialEq, Error)]
 pub enum ExprErrorVariant {
+    /// `expected` holds the kinds starting an expression along with the other kinds
+    /// accepted where the error happened
+    #[error("Expected {}, found {found}", expression_or(*expected))]
+    ExpectedExpression { expected: TokenSet, found: Found },
+    /// `expected` holds every kind of token accepted where the error happened
+    #[error("Expected {expected}, found {found}")]
+    Expected { expected: TokenSet, found: Found },
     #[error("A grouping expression was started here but was never closed")]
     UnclosedGrouping,
-    #[error("A token was expected here")]
-    ExpectedToken,
+    #[error("A call was started here but was never closed")]
+    UnclosedCall,
+    #[error("An index was started here but was never closed")]
+    UnclosedIndex,
+    #[error("A trailing comma is not allowed after the last argument")]
+    TrailingComma,
+    #[error("Range operators can't be chained")]
+    ChainedRange,
+}
+
+/// The kinds starting an expression are named as a whole
+fn expression_or(expected: TokenSet) -> String {
+    let others = expected.difference(EXPR_START);
+
+    if others.is_empty() {
+        "an expression".into()
+    } else {
+        format!("an expression or {others}")
+    }
 }
 
 #[derive(Debug, Error)]
@@ -20,4 +47,21 @@ impl ExprError {
     pub fn new(variant: ExprErrorVariant, context: BriseContext) -> Self {
         Self { variant, context }
     }
+
+    pub fn variant(&self) -> &ExprErrorVariant {
+        &self.variant
+    }
+
+    pub fn context(&self) -> &BriseContext {
+        &self.context
+    }
+
+    pub fn into_parts(self) -> (ExprErrorVariant, BriseContext) {
+        (self.variant, self.context)
+    }
+
+    /// Error message along with the source line where the error happened
+    pub fn render(&self, source_map: &SourceMap) -> String {
+        source_map.render(&self.context, &self.variant)
+    }
 }
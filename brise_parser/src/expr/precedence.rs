@@ -0,0 +1,102 @@
+use brise_syntax_tree::expr::binary::BinaryOperatorVariant;
+use brise_token::{TokenKind, TokenSet};
+
+/// Binding power of the operand of a unary operator: `-a * b` is `(-a) * b`,
+/// but `-a ** b` is `-(a ** b)`
+pub const UNARY_POWER: u8 = 10;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Associativity {
+    /// `a - b - c` is `(a - b) - c`
+    Left,
+    /// `a = b = c` is `a = (b = c)`
+    Right,
+    /// Can't be chained, `a..b..c` is parsed as a whole and reported as one error
+    None,
+}
+
+#[derive(Debug)]
+pub struct Operator {
+    pub token: TokenKind,
+    pub variant: BinaryOperatorVariant,
+    /// Operators with a higher power bind tighter
+    pub power: u8,
+    pub associativity: Associativity,
+}
+
+impl Operator {
+    const fn new(
+        token: TokenKind,
+        variant: BinaryOperatorVariant,
+        power: u8,
+        associativity: Associativity,
+    ) -> Self {
+        Self {
+            token,
+            variant,
+            power,
+            associativity,
+        }
+    }
+
+    /// Minimum power of the operators of the right operand
+    pub fn right_power(&self) -> u8 {
+        match self.associativity {
+            Associativity::Right => self.power,
+            Associativity::Left | Associativity::None => self.power + 1,
+        }
+    }
+}
+
+/// Every binary operator, from the loosest to the tightest
+pub static OPERATORS: [Operator; 23] = TABLE;
+
+/// Kinds of the tokens of the binary operators
+pub const OPERATOR_KINDS: TokenSet = {
+    let mut set = TokenSet::EMPTY;
+    let mut index = 0;
+
+    while index < TABLE.len() {
+        set = set.with(TABLE[index].token);
+        index += 1;
+    }
+
+    set
+};
+
+const TABLE: [Operator; 23] = {
+    use Associativity::{Left, None, Right};
+    use BinaryOperatorVariant as B;
+    use TokenKind as T;
+
+    [
+        Operator::new(T::Equal, B::Equal, 1, Right),
+        Operator::new(T::PlusEqual, B::PlusEqual, 1, Right),
+        Operator::new(T::MinusEqual, B::MinusEqual, 1, Right),
+        Operator::new(T::StarEqual, B::StarEqual, 1, Right),
+        Operator::new(T::SlashEqual, B::SlashEqual, 1, Right),
+        Operator::new(T::PercentEqual, B::PercentEqual, 1, Right),
+        Operator::new(T::BarGreater, B::Pipe, 2, Left),
+        Operator::new(T::DotDot, B::DotDot, 3, None),
+        Operator::new(T::DotDotEqual, B::DotDotEqual, 3, None),
+        Operator::new(T::BarBar, B::Or, 4, Left),
+        Operator::new(T::AmpersandAmpersand, B::And, 5, Left),
+        Operator::new(T::EqualEqual, B::EqualEqual, 6, Left),
+        Operator::new(T::BangEqual, B::BangEqual, 6, Left),
+        Operator::new(T::Less, B::Less, 7, Left),
+        Operator::new(T::LessEqual, B::LessEqual, 7, Left),
+        Operator::new(T::Greater, B::Greater, 7, Left),
+        Operator::new(T::GreaterEqual, B::GreaterEqual, 7, Left),
+        Operator::new(T::Plus, B::Plus, 8, Left),
+        Operator::new(T::Minus, B::Minus, 8, Left),
+        Operator::new(T::Star, B::Star, 9, Left),
+        Operator::new(T::Slash, B::Slash, 9, Left),
+        Operator::new(T::Percent, B::Percent, 9, Left),
+        Operator::new(T::StarStar, B::StarStar, 11, Right),
+    ]
+};
+
+/// Binary operator written with this token
+pub fn binary_operator(token: TokenKind) -> Option<&'static Operator> {
+    OPERATORS.iter().find(|operator| operator.token == token)
+}
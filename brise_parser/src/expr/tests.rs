@@ -1,12 +1,27 @@
-use std::collections::VecDeque;
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    num::NonZeroUsize,
+};
 
 use brise_syntax_tree::expr::{
+    binary::BinaryOperatorVariant,
     literal::{Literal, LiteralVariant, NumberLiteral},
     Expr, ExprVariant,
 };
-use brise_token::{BriseContext, Token, TokenVariant};
+use brise_token::{
+    BriseContext, Column, Line, SourceMap, Span, Token, TokenKind, TokenSet, TokenVariant,
+};
 
-use super::ExprParser;
+use super::{
+    error::ExprErrorVariant,
+    precedence::{Associativity, OPERATORS},
+    ExprParser, EXPR_START,
+};
+use crate::{
+    error::Found,
+    test_support::{expected, expected_after_operand, found},
+    tokens::TokenParser,
+};
 
 fn tokens_from_variant(variants: Vec<TokenVariant>) -> VecDeque<Token> {
     variants
@@ -17,16 +32,545 @@ fn tokens_from_variant(variants: Vec<TokenVariant>) -> VecDeque<Token> {
 
 #[test]
 fn number_literal() {
-    let tokens = vec![TokenVariant::Number(45.6)];
+    let tokens = vec![TokenVariant::Float(45.6)];
     let mut tokens = tokens_from_variant(tokens);
 
     let expr = ExprParser::parse(&mut tokens).unwrap();
 
     let expected_expr = Expr::new(ExprVariant::Literal(Literal::new(
-        LiteralVariant::Number(NumberLiteral::new_with_custom_id(45.6, 0)),
+        LiteralVariant::Float(NumberLiteral::new(45.6)),
         BriseContext::default(),
     )));
 
     assert!(tokens.is_empty());
     assert_eq!(expected_expr, expr);
 }
+
+#[test]
+fn composite_expression_span() {
+    let mut tokens =
+        TokenParser::parse_deque(&mut SourceMap::default(), "(a + b)\n * -abc".into()).unwrap();
+
+    let expr = ExprParser::parse(&mut tokens).unwrap();
+
+    assert_eq!(Span::new(0, 15), expr.span());
+    let ExprVariant::Binary(binary) = expr.variant() else {
+        panic!("expected a binary expression, got {expr:?}");
+    };
+    assert_eq!(Span::new(0, 7), binary.left().span());
+    assert_eq!(Span::new(11, 15), binary.right().span());
+    assert_eq!(Span::new(9, 10), binary.operator().span());
+
+    let context = binary.right().context();
+    assert_eq!(
+        (
+            Line::from(NonZeroUsize::new(2).unwrap()),
+            Column::from(NonZeroUsize::new(4).unwrap())
+        ),
+        (context.line(), context.col())
+    );
+}
+
+#[test]
+fn formatted_string_literal() {
+    let mut tokens =
+        TokenParser::parse_deque(&mut SourceMap::default(), "$\"sum: {a + b}!\"".into()).unwrap();
+
+    let expr = ExprParser::parse(&mut tokens).unwrap();
+
+    let ExprVariant::Literal(literal) = expr.variant() else {
+        panic!("expected a literal, got {expr:?}");
+    };
+    let LiteralVariant::FormattedString(exprs) = literal.variant() else {
+        panic!("expected a formatted string, got {literal:?}");
+    };
+
+    assert_eq!(3, exprs.len());
+    assert!(matches!(
+        exprs[0].variant(),
+        ExprVariant::Literal(literal) if *literal.variant() == LiteralVariant::String("sum: ".into())
+    ));
+    assert!(matches!(exprs[1].variant(), ExprVariant::Binary(_)));
+    assert_eq!(Span::new(8, 13), exprs[1].span());
+    assert!(matches!(
+        exprs[2].variant(),
+        ExprVariant::Literal(literal) if *literal.variant() == LiteralVariant::String("!".into())
+    ));
+}
+
+#[test]
+fn formatted_string_with_trailing_tokens() {
+    let mut tokens =
+        TokenParser::parse_deque(&mut SourceMap::default(), "$\"{a b}\"".into()).unwrap();
+
+    assert!(ExprParser::parse(&mut tokens).is_err());
+}
+
+/// Renders an expression with explicit parentheses to check precedence and associativity
+fn to_sexpr(expr: &Expr) -> String {
+    match expr.variant() {
+        ExprVariant::Binary(binary) => format!(
+            "({:?} {} {})",
+            binary.operator().variant(),
+            to_sexpr(binary.left()),
+            to_sexpr(binary.right())
+        ),
+        ExprVariant::Unary(unary) => {
+            format!(
+                "({:?} {})",
+                unary.operator().variant(),
+                to_sexpr(unary.expr())
+            )
+        }
+        ExprVariant::Grouping(grouping) => to_sexpr(grouping.expr()),
+        ExprVariant::Literal(literal) => match literal.variant() {
+            LiteralVariant::Integer(value) => value.to_string(),
+            variant => format!("{variant:?}"),
+        },
+        ExprVariant::Identifier(identifier) => identifier.identity().to_string(),
+        ExprVariant::Call(call) => format!(
+            "(call {}{})",
+            to_sexpr(call.callee()),
+            call.args()
+                .iter()
+                .map(|arg| format!(" {}", to_sexpr(arg)))
+                .collect::<String>()
+        ),
+        ExprVariant::Field(field) => format!(
+            "(field {} {})",
+            to_sexpr(field.object()),
+            field.name().identity()
+        ),
+        ExprVariant::Index(index) => format!(
+            "(index {} {})",
+            to_sexpr(index.object()),
+            to_sexpr(index.index())
+        ),
+        ExprVariant::Error(_) => "<error>".into(),
+    }
+}
+
+fn parse_sexpr(input: &str) -> String {
+    let mut tokens = TokenParser::parse_deque(&mut SourceMap::default(), input.into()).unwrap();
+
+    let expr = ExprParser::parse(&mut tokens).unwrap();
+
+    assert!(tokens.is_empty(), "{input} was not fully parsed");
+    to_sexpr(&expr)
+}
+
+#[test]
+fn operator_precedence() {
+    let inputs = [
+        ("a = b += c", "(Equal a (PlusEqual b c))"),
+        ("a -= b |> c", "(MinusEqual a (Pipe b c))"),
+        ("a |> b |> c", "(Pipe (Pipe a b) c)"),
+        ("a |> 0..b", "(Pipe a (DotDot 0 b))"),
+        ("0..=a || b", "(DotDotEqual 0 (Or a b))"),
+        ("a || b && c", "(Or a (And b c))"),
+        ("a && b == c", "(And a (EqualEqual b c))"),
+        ("a == b < c", "(EqualEqual a (Less b c))"),
+        ("a < b + c", "(Less a (Plus b c))"),
+        ("a + b % c", "(Plus a (Percent b c))"),
+        ("a % -b", "(Percent a (Minus b))"),
+        ("-a ** b", "(Minus (StarStar a b))"),
+        ("a ** b ** c", "(StarStar a (StarStar b c))"),
+        ("a ** -b", "(StarStar a (Minus b))"),
+        ("a *= (b = c)", "(StarEqual a (Equal b c))"),
+        ("a /= b %= c", "(SlashEqual a (PercentEqual b c))"),
+        ("a * b / c", "(Slash (Star a b) c)"),
+        ("a || b || c && d", "(Or (Or a b) (And c d))"),
+        ("-a * b ** c", "(Star (Minus a) (StarStar b c))"),
+        ("a..b |> c", "(Pipe (DotDot a b) c)"),
+    ];
+
+    for (input, expected) in inputs {
+        assert_eq!(expected, parse_sexpr(input), "{input}");
+    }
+}
+
+#[test]
+fn expr_serde_round_trip() {
+    let mut tokens = TokenParser::parse_deque(
+        &mut SourceMap::default(),
+        "-(a + 1) * $\"x{b}\" == true".into(),
+    )
+    .unwrap();
+    let expr = ExprParser::parse(&mut tokens).unwrap();
+
+    let json = serde_json::to_value(&expr).unwrap();
+    let read: Expr = serde_json::from_value(json.clone()).unwrap();
+
+    assert_eq!(expr, read);
+    assert_eq!("EqualEqual", json["Binary"]["operator"]["variant"]);
+    assert_eq!(
+        serde_json::json!({ "Literal": {
+            "variant": "True",
+            "context": { "line": 1, "col": 23, "span": { "start": 22, "end": 26 }, "file": 0 }
+        }}),
+        json["Binary"]["right"]
+    );
+}
+
+#[test]
+fn infinite_float_expr_serde_round_trip() {
+    let mut tokens = tokens_from_variant(vec![TokenVariant::Float(f64::NEG_INFINITY)]);
+    let expr = ExprParser::parse(&mut tokens).unwrap();
+
+    let json = serde_json::to_value(&expr).unwrap();
+    let read: Expr = serde_json::from_value(json.clone()).unwrap();
+
+    assert_eq!(expr, read);
+    assert_eq!("-inf", json["Literal"]["variant"]["Float"]);
+}
+
+#[test]
+fn parses_of_the_same_source_are_equal() {
+    let parse = || {
+        let mut tokens =
+            TokenParser::parse_deque(&mut SourceMap::default(), "a * 1.5 + -2.5".into()).unwrap();
+        ExprParser::parse(&mut tokens).unwrap()
+    };
+    let (first, second) = (parse(), parse());
+
+    assert_eq!(first, second);
+    assert_ne!(first.id(), second.id());
+    assert_eq!(first.id(), first.clone().id());
+
+    // Side tables are keyed by node, not by structure
+    let types = HashMap::from([(first.id(), "number"), (second.id(), "float")]);
+    assert_eq!(Some(&"number"), types.get(&first.id()));
+    assert_eq!(Some(&"float"), types.get(&second.id()));
+}
+
+#[test]
+fn number_literals_compare_bit_patterns() {
+    assert_eq!(NumberLiteral::new(1.5), NumberLiteral::new(1.5));
+    assert_eq!(NumberLiteral::new(f64::NAN), NumberLiteral::new(-f64::NAN));
+    assert_ne!(NumberLiteral::new(0.0), NumberLiteral::new(-0.0));
+}
+
+/// Stated apart from the table, the match fails to compile when a variant is added
+fn expected_associativity(variant: BinaryOperatorVariant) -> Associativity {
+    use BinaryOperatorVariant as B;
+
+    match variant {
+        B::Equal
+        | B::PlusEqual
+        | B::MinusEqual
+        | B::StarEqual
+        | B::SlashEqual
+        | B::PercentEqual
+        | B::StarStar => Associativity::Right,
+        B::DotDot | B::DotDotEqual => Associativity::None,
+        B::Pipe
+        | B::Or
+        | B::And
+        | B::EqualEqual
+        | B::BangEqual
+        | B::Less
+        | B::LessEqual
+        | B::Greater
+        | B::GreaterEqual
+        | B::Plus
+        | B::Minus
+        | B::Star
+        | B::Slash
+        | B::Percent => Associativity::Left,
+    }
+}
+
+#[test]
+fn every_operator_associativity() {
+    let variants: HashSet<_> = OPERATORS.iter().map(|operator| operator.variant).collect();
+    assert_eq!(OPERATORS.len(), variants.len());
+
+    for operator in &OPERATORS {
+        let input = format!("a {0} b {0} c", operator.token.text().unwrap());
+        let mut tokens =
+            TokenParser::parse_deque(&mut SourceMap::default(), input.clone()).unwrap();
+        let (expr, errors) = ExprParser::parse_partial(&mut tokens);
+        let errors: Vec<_> = errors
+            .into_iter()
+            .map(|error| error.into_parts().0)
+            .collect();
+
+        let name = format!("{:?}", operator.variant);
+        let (expected, expected_errors) = match expected_associativity(operator.variant) {
+            Associativity::Left => (format!("({name} ({name} a b) c)"), vec![]),
+            Associativity::Right => (format!("({name} a ({name} b c))"), vec![]),
+            Associativity::None => ("<error>".into(), vec![ExprErrorVariant::ChainedRange]),
+        };
+
+        assert_eq!(
+            expected_associativity(operator.variant),
+            operator.associativity
+        );
+        assert_eq!(expected, to_sexpr(&expr), "{input}");
+        assert_eq!(expected_errors, errors, "{input}");
+        assert!(tokens.is_empty(), "{input}");
+    }
+}
+
+#[test]
+fn operators_of_a_level_share_the_associativity() {
+    for operator in &OPERATORS {
+        for other in OPERATORS
+            .iter()
+            .filter(|other| other.power == operator.power)
+        {
+            assert_eq!(operator.associativity, other.associativity);
+
+            // Mixing two operators of a level follows the associativity too
+            let input = format!(
+                "a {} b {} c",
+                operator.token.text().unwrap(),
+                other.token.text().unwrap()
+            );
+            let expected = match operator.associativity {
+                Associativity::Left => {
+                    format!("({:?} ({:?} a b) c)", other.variant, operator.variant)
+                }
+                Associativity::Right => {
+                    format!("({:?} a ({:?} b c))", operator.variant, other.variant)
+                }
+                Associativity::None => "<error>".into(),
+            };
+            let mut tokens =
+                TokenParser::parse_deque(&mut SourceMap::default(), input.clone()).unwrap();
+            let (expr, _) = ExprParser::parse_partial(&mut tokens);
+
+            assert_eq!(expected, to_sexpr(&expr), "{input}");
+        }
+    }
+}
+
+#[test]
+fn postfix_chains() {
+    let inputs = [
+        ("f()", "(call f)"),
+        ("a.b(c)[d](e)", "(call (index (call (field a b) c) d) e)"),
+        ("f(a, b + 1, g(c))", "(call f a (Plus b 1) (call g c))"),
+        ("-a.b ** c[0]", "(Minus (StarStar (field a b) (index c 0)))"),
+        ("(a)(b).c", "(field (call a b) c)"),
+        ("a[b = c]", "(index a (Equal b c))"),
+    ];
+
+    for (input, expected) in inputs {
+        assert_eq!(expected, parse_sexpr(input), "{input}");
+    }
+}
+
+#[test]
+fn postfix_spans() {
+    let mut tokens =
+        TokenParser::parse_deque(&mut SourceMap::default(), "a.b(c)[d]".into()).unwrap();
+
+    let expr = ExprParser::parse(&mut tokens).unwrap();
+
+    assert_eq!(Span::new(0, 9), expr.span());
+    let ExprVariant::Index(index) = expr.variant() else {
+        panic!("expected an index expression, got {expr:?}");
+    };
+    assert_eq!(Span::new(6, 9), index.span());
+    let ExprVariant::Call(call) = index.object().variant() else {
+        panic!("expected a call, got {expr:?}");
+    };
+    assert_eq!(Span::new(3, 6), call.span());
+    assert_eq!(Span::new(0, 6), index.object().span());
+}
+
+/// The kinds starting an expression are always expected along with `kinds`
+fn expected_expression(kinds: &[TokenKind], found: Found) -> ExprErrorVariant {
+    ExprErrorVariant::ExpectedExpression {
+        expected: TokenSet::new(kinds).union(EXPR_START),
+        found,
+    }
+}
+
+#[test]
+fn postfix_errors() {
+    let inputs = [
+        ("f(a, b", ExprErrorVariant::UnclosedCall, 1..2),
+        ("f(a,)", ExprErrorVariant::TrailingComma, 3..4),
+        (
+            "f(a b)",
+            expected_after_operand(&[TokenKind::Comma, TokenKind::RightParen], found("b")),
+            4..5,
+        ),
+        (
+            "f(,)",
+            expected_expression(&[TokenKind::RightParen], found(",")),
+            2..3,
+        ),
+        ("a[0", ExprErrorVariant::UnclosedIndex, 1..2),
+        (
+            "a[0;",
+            expected_after_operand(&[TokenKind::RightBracket], found(";")),
+            3..4,
+        ),
+        ("a.1", expected(&[TokenKind::Identifier], found("1")), 2..3),
+        (
+            "a.",
+            expected(&[TokenKind::Identifier], Found::EndOfInput),
+            1..2,
+        ),
+        ("a +", expected_expression(&[], Found::EndOfInput), 2..3),
+        (
+            "(a b",
+            expected_after_operand(&[TokenKind::RightParen], found("b")),
+            3..4,
+        ),
+        ("(a", ExprErrorVariant::UnclosedGrouping, 0..1),
+    ];
+
+    for (input, variant, span) in inputs {
+        let mut tokens = TokenParser::parse_deque(&mut SourceMap::default(), input.into()).unwrap();
+        let (error_variant, context) = ExprParser::parse(&mut tokens).unwrap_err().into_parts();
+
+        assert_eq!(
+            (variant, Span::from(span)),
+            (error_variant, context.span()),
+            "{input}"
+        );
+    }
+}
+
+fn parse_partial(input: &str) -> (String, Vec<(ExprErrorVariant, Span)>) {
+    let mut tokens = TokenParser::parse_deque(&mut SourceMap::default(), input.into()).unwrap();
+    let (expr, errors) = ExprParser::parse_partial(&mut tokens);
+    let errors = errors
+        .into_iter()
+        .map(|error| {
+            let (variant, context) = error.into_parts();
+            (variant, context.span())
+        })
+        .collect();
+
+    (to_sexpr(&expr), errors)
+}
+
+#[test]
+fn errors_are_replaced_by_error_nodes() {
+    let (sexpr, errors) = parse_partial("f(a b, c).d + )");
+
+    assert_eq!("(Plus (field (call f a) d) <error>)", sexpr);
+    assert_eq!(
+        vec![
+            (
+                expected_after_operand(&[TokenKind::Comma, TokenKind::RightParen], found("b")),
+                Span::new(4, 5)
+            ),
+            (expected_expression(&[], found(")")), Span::new(14, 15)),
+        ],
+        errors
+    );
+}
+
+#[test]
+fn every_interpolation_error_is_reported() {
+    let (_, errors) = parse_partial("$\"{a +} {(b}\"");
+
+    assert_eq!(2, errors.len());
+    assert_eq!(expected_expression(&[], Found::EndOfInput), errors[0].0);
+    assert_eq!(ExprErrorVariant::UnclosedGrouping, errors[1].0);
+}
+
+#[test]
+fn error_messages_name_the_found_token() {
+    let mut tokens = TokenParser::parse_deque(&mut SourceMap::default(), ")".into()).unwrap();
+    let error = ExprParser::parse(&mut tokens).unwrap_err();
+
+    assert_eq!(
+        "Expected an expression, found `)`",
+        error.variant().to_string()
+    );
+    assert_eq!(1, tokens.len());
+}
+
+#[test]
+fn error_messages_list_the_expected_tokens() {
+    let inputs = [
+        (
+            "f(a;",
+            "Expected one of `(`, `)`, `[`, `,`, `.`, `-`, `+`, `/`, `*`, `**`, `%`, `+=`, \
+             `-=`, `*=`, `/=`, `%=`, `..`, `..=`, `|>`, `!=`, `=`, `==`, `>`, `>=`, `<`, \
+             `<=`, `&&`, `||`, found `;`",
+        ),
+        ("f(;", "Expected an expression or `)`, found `;`"),
+        ("a[", "Expected an expression, found the end of the input"),
+    ];
+
+    for (input, message) in inputs {
+        let mut tokens = TokenParser::parse_deque(&mut SourceMap::default(), input.into()).unwrap();
+        let error = ExprParser::parse(&mut tokens).unwrap_err();
+
+        assert_eq!(message, error.variant().to_string(), "{input}");
+    }
+}
+
+#[test]
+fn chained_ranges_are_one_error() {
+    let (sexpr, errors) = parse_partial("a..b..=c");
+
+    assert_eq!("<error>", sexpr);
+    assert_eq!(
+        vec![(ExprErrorVariant::ChainedRange, Span::new(4, 7))],
+        errors
+    );
+}
+
+#[test]
+fn longer_range_chains_are_one_error() {
+    let (sexpr, errors) = parse_partial("a..b..c..=d..e");
+
+    assert_eq!("<error>", sexpr);
+    assert_eq!(
+        vec![(ExprErrorVariant::ChainedRange, Span::new(4, 6))],
+        errors
+    );
+}
+
+#[test]
+fn bad_arguments_and_indexes_skip_to_their_closing_token() {
+    let inputs = [
+        (
+            "f(;)",
+            "(call f <error>)",
+            expected_expression(&[TokenKind::RightParen], found(";")),
+        ),
+        (
+            "f(,)",
+            "(call f <error>)",
+            expected_expression(&[TokenKind::RightParen], found(",")),
+        ),
+        (
+            "g(f(,))",
+            "(call g (call f <error>))",
+            expected_expression(&[TokenKind::RightParen], found(",")),
+        ),
+        (
+            "a[;]",
+            "(index a <error>)",
+            expected_expression(&[], found(";")),
+        ),
+        (
+            "f(a b; c)",
+            "(call f a)",
+            expected_after_operand(&[TokenKind::RightParen, TokenKind::Comma], found("b")),
+        ),
+    ];
+
+    for (input, sexpr, error) in inputs {
+        let mut tokens = TokenParser::parse_deque(&mut SourceMap::default(), input.into()).unwrap();
+        let (expr, errors) = ExprParser::parse_partial(&mut tokens);
+        let errors: Vec<_> = errors
+            .into_iter()
+            .map(|error| error.into_parts().0)
+            .collect();
+
+        assert_eq!(sexpr, to_sexpr(&expr), "{input}");
+        assert_eq!(vec![error], errors, "{input}");
+        assert!(tokens.is_empty(), "{input}");
+    }
+}
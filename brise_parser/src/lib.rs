@@ -1,14 +1,77 @@
-use brise_token::{BriseFile, Token};
-use error::ParserError;
+use brise_syntax_tree::program::Program;
+use brise_token::{BriseFile, SourceMap, TextEdit, Token};
+pub use error::{Found, ParserError};
+use stmt::ParsedProgram;
+use tokens::LexedTokens;
 
 mod error;
-mod tokens;
-mod expr;
+pub mod expr;
+mod state;
+pub mod stmt;
+#[cfg(test)]
+mod test_support;
+pub mod tokens;
 
-pub fn parse_tokens(input: String) -> Result<Vec<Token>, ParserError> {
-    tokens::TokenParser::parse(input)
+/// Lexes and parses the input, the first error found is returned
+pub fn parse_program(source_map: &mut SourceMap, input: String) -> Result<Program, ParserError> {
+    stmt::StmtParser::parse_program(source_map, input)
 }
 
-pub fn parser_file_tokens(file: impl Into<BriseFile>) -> Result<Vec<Token>, ParserError> {
-    tokens::TokenParser::parse_file(file)
+pub fn parse_program_file(
+    source_map: &mut SourceMap,
+    file: impl Into<BriseFile>,
+) -> Result<Program, ParserError> {
+    stmt::StmtParser::parse_program_file(source_map, file)
+}
+
+/// Lexes and parses the input past its errors, every error is returned along with
+/// the program
+pub fn parse_program_partial(source_map: &mut SourceMap, input: String) -> ParsedProgram {
+    stmt::StmtParser::parse_program_partial(source_map, input)
+}
+
+pub fn parse_program_file_partial(
+    source_map: &mut SourceMap,
+    file: impl Into<BriseFile>,
+) -> Result<ParsedProgram, ParserError> {
+    stmt::StmtParser::parse_program_file_partial(source_map, file)
+}
+
+pub fn parse_tokens(source_map: &mut SourceMap, input: String) -> Result<Vec<Token>, ParserError> {
+    tokens::TokenParser::parse(source_map, input)
+}
+
+/// Never fails, invalid input is lexed as `Error` tokens and reported along with the tokens
+pub fn lex_tokens(source_map: &mut SourceMap, input: String) -> LexedTokens {
+    tokens::TokenParser::lex(source_map, input)
+}
+
+/// Applies the edit to the source of the tokens, lexing again only the tokens around it
+pub fn relex_tokens(
+    source_map: &mut SourceMap,
+    previous: LexedTokens,
+    edit: &TextEdit,
+) -> LexedTokens {
+    tokens::TokenParser::relex(source_map, previous, edit)
+}
+
+/// Keeps every whitespace and comment around the tokens, so that the input can be
+/// rebuilt from the tokens
+pub fn parse_tokens_lossless(
+    source_map: &mut SourceMap,
+    input: String,
+) -> Result<Vec<Token>, ParserError> {
+    tokens::TokenParser::parse_lossless(source_map, input)
+}
+
+/// Renders the tokens back into source code lexing to the same tokens
+pub fn print_tokens(tokens: &[Token]) -> String {
+    tokens::printer::TokenPrinter::print(tokens)
+}
+
+pub fn parser_file_tokens(
+    source_map: &mut SourceMap,
+    file: impl Into<BriseFile>,
+) -> Result<Vec<Token>, ParserError> {
+    tokens::TokenParser::parse_file(source_map, file)
 }
@@ -0,0 +1,89 @@
+use std::collections::VecDeque;
+
+use brise_token::{BriseContext, Token, TokenKind, TokenSet, TokenVariant};
+
+use crate::error::Found;
+
+/// Error of a parser holding a [`ParserState`]
+pub(crate) trait SyntaxError {
+    /// A token, or the end of the input, met in place of the `expected` kinds
+    fn expected(expected: TokenSet, found: Found, context: BriseContext) -> Self;
+}
+
+/// Tokens left to parse, along with what is needed to report an error at the next one.
+/// Shared by the statement and expression parsers.
+#[derive(Debug)]
+pub(crate) struct ParserState<'a, E> {
+    pub input: &'a mut VecDeque<Token>,
+    /// Context of the last token consumed, errors at the end of the input point to it
+    pub last_context: BriseContext,
+    pub errors: Vec<E>,
+    /// Kinds checked for since the last token was consumed, reported when none is found
+    pub expected: TokenSet,
+    /// Set once an error is reported, the errors following from it are not reported
+    pub recovering: bool,
+}
+
+impl<'a, E: SyntaxError> ParserState<'a, E> {
+    pub fn new(input: &'a mut VecDeque<Token>, last_context: BriseContext) -> Self {
+        Self {
+            input,
+            last_context,
+            errors: vec![],
+            expected: TokenSet::EMPTY,
+            recovering: false,
+        }
+    }
+
+    /// A kind which isn't next is added to the expected kinds
+    pub fn next_is(&mut self, kind: TokenKind) -> bool {
+        let found = self.input.front().is_some_and(|token| token.kind() == kind);
+        if !found {
+            self.expected.insert(kind);
+        }
+
+        found
+    }
+
+    /// The next token, or the end of the input at the last token
+    pub fn found(&self) -> (Found, BriseContext) {
+        let context = self.input.front().map_or_else(
+            || self.last_context.clone(),
+            |token| token.context().clone(),
+        );
+
+        (self.input.front().into(), context)
+    }
+
+    /// Reports the next token, or the end of the input, in place of the kinds checked for
+    pub fn unexpected(&mut self) {
+        let (found, context) = self.found();
+
+        self.error(E::expected(self.expected, found, context));
+    }
+
+    /// Only the first error is reported until `recovering` is cleared. An error at an
+    /// `Error` token follows from the one the lexer reported for it.
+    pub fn error(&mut self, error: E) {
+        let at_lexing_error = self
+            .input
+            .front()
+            .is_some_and(|token| token.variant() == &TokenVariant::Error);
+        if !self.recovering && !at_lexing_error {
+            self.errors.push(error);
+        }
+        self.recovering = true;
+    }
+
+    /// Consuming an `Error` token starts recovering, the lexer has already reported it
+    pub fn pop_front(&mut self) -> Option<Token> {
+        let token = self.input.pop_front()?;
+        self.last_context = token.context().clone();
+        self.expected = TokenSet::EMPTY;
+        if token.variant() == &TokenVariant::Error {
+            self.recovering = true;
+        }
+
+        Some(token)
+    }
+}
@@ -0,0 +1,477 @@
+use std::collections::VecDeque;
+
+use brise_syntax_tree::{
+    expr::{error::ErrorExpr, identifier::Identifier, Expr},
+    program::Program,
+    stmt::{
+        block::Block,
+        break_stmt::BreakStmt,
+        continue_stmt::ContinueStmt,
+        expr_stmt::ExprStmt,
+        fn_decl::FnDecl,
+        for_stmt::ForStmt,
+        if_stmt::{ElseBranch, IfStmt},
+        let_stmt::LetStmt,
+        loop_stmt::LoopStmt,
+        return_stmt::ReturnStmt,
+        while_stmt::WhileStmt,
+        Stmt,
+    },
+};
+use brise_token::{
+    BriseContext, BriseFile, Column, Line, RawString, SourceMap, Token, TokenKind, TokenSet,
+    TokenVariant,
+};
+use error::{StmtError, StmtErrorVariant};
+
+use crate::{
+    error::{Found, ParserError},
+    expr::ExprParser,
+    state::{ParserState, SyntaxError},
+    tokens::{error::ParsingErrors, LexedTokens, TokenParser},
+};
+
+pub mod error;
+#[cfg(test)]
+mod tests;
+
+/// A program parsed past its errors, the statements which failed to parse are
+/// replaced by `ExprVariant::Error` placeholders
+#[derive(Debug)]
+pub struct ParsedProgram {
+    program: Program,
+    errors: Vec<ParserError>,
+}
+
+impl ParsedProgram {
+    pub fn program(&self) -> &Program {
+        &self.program
+    }
+
+    /// Lexing and syntax errors, in the order of the input
+    pub fn errors(&self) -> &[ParserError] {
+        &self.errors
+    }
+
+    pub fn into_parts(self) -> (Program, Vec<ParserError>) {
+        (self.program, self.errors)
+    }
+}
+
+/// Parses statements out of tokens, the expressions are parsed by [`ExprParser`]
+#[derive(Debug)]
+pub struct StmtParser<'a> {
+    /// `recovering` is cleared at the start of each statement
+    state: ParserState<'a, ParserError>,
+}
+
+impl StmtParser<'_> {
+    /// Lexes and parses a whole source, stopping at the first error
+    pub fn parse_program(
+        source_map: &mut SourceMap,
+        input: String,
+    ) -> Result<Program, ParserError> {
+        let lexed = TokenParser::lex(source_map, input);
+
+        Self::parse_lexed(source_map, lexed)
+    }
+
+    pub fn parse_program_file(
+        source_map: &mut SourceMap,
+        file: impl Into<BriseFile>,
+    ) -> Result<Program, ParserError> {
+        let lexed = TokenParser::lex_file(source_map, file)?;
+
+        Self::parse_lexed(source_map, lexed)
+    }
+
+    /// Lexes and parses a whole source, reporting every error of the input
+    pub fn parse_program_partial(source_map: &mut SourceMap, input: String) -> ParsedProgram {
+        let lexed = TokenParser::lex(source_map, input);
+
+        Self::parse_lexed_partial(source_map, lexed)
+    }
+
+    /// Only fails when the file can't be read
+    pub fn parse_program_file_partial(
+        source_map: &mut SourceMap,
+        file: impl Into<BriseFile>,
+    ) -> Result<ParsedProgram, ParserError> {
+        let lexed = TokenParser::lex_file(source_map, file)?;
+
+        Ok(Self::parse_lexed_partial(source_map, lexed))
+    }
+
+    /// Lexing errors are reported together, before any syntax error
+    fn parse_lexed(source_map: &SourceMap, lexed: LexedTokens) -> Result<Program, ParserError> {
+        if !lexed.errors().is_empty() {
+            let (_, errors, _) = lexed.into_parts();
+            return Err(ParsingErrors::from(errors).into());
+        }
+
+        let (program, errors) = Self::parse_lexed_partial(source_map, lexed).into_parts();
+        match errors.into_iter().next() {
+            Some(error) => Err(error),
+            None => Ok(program),
+        }
+    }
+
+    fn parse_lexed_partial(source_map: &SourceMap, lexed: LexedTokens) -> ParsedProgram {
+        let file = lexed.file();
+        let (tokens, lexing_errors, _) = lexed.into_parts();
+
+        let mut tokens = VecDeque::from(tokens);
+        let mut parser = StmtParser::new(&mut tokens);
+        let mut stmts = vec![];
+
+        loop {
+            let doc = parser.parse_doc();
+            if parser.state.input.is_empty() {
+                break;
+            }
+            stmts.push(parser.parse_stmt(doc));
+        }
+
+        let mut errors: Vec<ParserError> = lexing_errors
+            .into_iter()
+            .map(|error| ParsingErrors::from(error).into())
+            .collect();
+        errors.append(&mut parser.state.errors);
+        errors.sort_by_key(ParserError::start);
+
+        let span = 0..source_map.text(file).len();
+        let context = BriseContext::new(file, span, Line::default(), Column::default());
+
+        ParsedProgram {
+            program: Program::new(stmts, context),
+            errors,
+        }
+    }
+}
+
+impl<'a> StmtParser<'a> {
+    /// Parses one statement from the front of the tokens, stopping at the first error
+    pub fn parse(tokens: &'a mut VecDeque<Token>) -> Result<Stmt, ParserError> {
+        let mut parser = Self::new(tokens);
+        let doc = parser.parse_doc();
+        let stmt = parser.parse_stmt(doc);
+
+        match parser.state.errors.into_iter().next() {
+            Some(error) => Err(error),
+            None => Ok(stmt),
+        }
+    }
+
+    fn new(input: &'a mut VecDeque<Token>) -> Self {
+        Self {
+            state: ParserState::new(input, BriseContext::default()),
+        }
+    }
+
+    /// The doc comments before the statement have already been consumed. A statement
+    /// which fails to parse is skipped and replaced by an error expression.
+    fn parse_stmt(&mut self, doc: Vec<RawString>) -> Stmt {
+        let remaining = self.state.input.len();
+        let start = self.state.input.front().map_or_else(
+            || self.state.last_context.clone(),
+            |token| token.context().clone(),
+        );
+        self.state.recovering = false;
+        self.state.expected = TokenSet::EMPTY;
+
+        if let Some(stmt) = self.parse_stmt_variant(doc) {
+            return stmt;
+        }
+
+        self.synchronize();
+        // A token which can't start anything, like a stray `}`, is skipped on its own
+        if self.state.input.len() == remaining {
+            self.state.pop_front();
+        }
+        let context = start.to(&self.state.last_context);
+
+        ExprStmt::new(ErrorExpr::new(context.clone()).into(), context).into()
+    }
+
+    fn parse_stmt_variant(&mut self, doc: Vec<RawString>) -> Option<Stmt> {
+        let stmt = match self.state.input.front().map(Token::variant) {
+            Some(TokenVariant::Let) => self.parse_let()?.into(),
+            Some(TokenVariant::LeftBrace) => self.parse_block()?.into(),
+            Some(TokenVariant::If) => self.parse_if()?.into(),
+            Some(TokenVariant::While) => self.parse_while()?.into(),
+            Some(TokenVariant::Loop) => self.parse_loop()?.into(),
+            Some(TokenVariant::For) => self.parse_for()?.into(),
+            Some(TokenVariant::Break) => {
+                let start = self.state.pop_front().unwrap().into();
+                BreakStmt::new(self.semicolon(&start)?).into()
+            }
+            Some(TokenVariant::Continue) => {
+                let start = self.state.pop_front().unwrap().into();
+                ContinueStmt::new(self.semicolon(&start)?).into()
+            }
+            Some(TokenVariant::Return) => self.parse_return()?.into(),
+            Some(TokenVariant::Fn) => self.parse_fn(doc)?.into(),
+            _ => {
+                let expr = self.parse_expr();
+                let context = self.semicolon(&expr.context())?;
+                ExprStmt::new(expr, context).into()
+            }
+        };
+
+        Some(stmt)
+    }
+
+    /// Skips the rest of a statement which failed to parse: up to and including its `;`,
+    /// or up to the `}` closing its block or the keyword starting the next statement
+    fn synchronize(&mut self) {
+        let mut depth = 0usize;
+
+        while let Some(token) = self.state.input.front() {
+            match token.variant() {
+                TokenVariant::Semicolon if depth == 0 => {
+                    self.state.pop_front();
+                    return;
+                }
+                TokenVariant::RightBrace if depth == 0 => return,
+                TokenVariant::RightBrace => depth -= 1,
+                TokenVariant::LeftBrace => depth += 1,
+                variant if depth == 0 && STMT_KEYWORDS.contains(variant.kind()) => return,
+                _ => {}
+            }
+            self.state.pop_front();
+        }
+    }
+
+    /// Doc comments are only kept before a function, the others are dropped
+    fn parse_doc(&mut self) -> Vec<RawString> {
+        let mut doc = vec![];
+
+        while let Some(TokenVariant::DocComment(text)) =
+            self.state.input.front().map(Token::variant)
+        {
+            doc.push(*text);
+            self.state.pop_front();
+        }
+
+        doc
+    }
+
+    /// `let a = 1;` or `let a;`
+    fn parse_let(&mut self) -> Option<LetStmt> {
+        let start: BriseContext = self.state.pop_front().unwrap().into();
+        let name = self.identifier()?;
+
+        let value = if self.state.next_is(TokenKind::Equal) {
+            self.state.pop_front();
+            Some(self.parse_expr())
+        } else {
+            None
+        };
+        let context = self.semicolon(&start)?;
+
+        Some(LetStmt::new(name, value, context))
+    }
+
+    /// A block left open at the end of the input is reported, and kept with the
+    /// statements parsed so far
+    fn parse_block(&mut self) -> Option<Block> {
+        if !self.state.next_is(TokenKind::LeftBrace) {
+            self.state.unexpected();
+            return None;
+        }
+        let left_brace = self.state.pop_front().unwrap();
+        let mut stmts = vec![];
+
+        loop {
+            let doc = self.parse_doc();
+
+            match self.state.input.front().map(Token::variant) {
+                Some(TokenVariant::RightBrace) => {
+                    self.state.pop_front();
+                    break;
+                }
+                Some(_) => stmts.push(self.parse_stmt(doc)),
+                None => {
+                    self.error(
+                        StmtErrorVariant::UnclosedBlock,
+                        left_brace.context().clone(),
+                    );
+                    break;
+                }
+            }
+        }
+        let context = left_brace.context().to(&self.state.last_context);
+
+        Some(Block::new(stmts, context))
+    }
+
+    /// `else if` chains are nested: the `if` following `else` is its own statement
+    fn parse_if(&mut self) -> Option<IfStmt> {
+        let start: BriseContext = self.state.pop_front().unwrap().into();
+        let condition = self.parse_expr();
+        let then_branch = self.parse_block()?;
+
+        let else_branch = if self.state.next_is(TokenKind::Else) {
+            self.state.pop_front();
+
+            if self.state.next_is(TokenKind::If) {
+                Some(ElseBranch::If(Box::new(self.parse_if()?)))
+            } else {
+                Some(ElseBranch::Block(self.parse_block()?))
+            }
+        } else {
+            None
+        };
+        let end = else_branch
+            .as_ref()
+            .map_or(then_branch.context(), ElseBranch::context);
+        let context = start.to(end);
+
+        Some(IfStmt::new(condition, then_branch, else_branch, context))
+    }
+
+    fn parse_while(&mut self) -> Option<WhileStmt> {
+        let start: BriseContext = self.state.pop_front().unwrap().into();
+        let condition = self.parse_expr();
+        let body = self.parse_block()?;
+        let context = start.to(body.context());
+
+        Some(WhileStmt::new(condition, body, context))
+    }
+
+    fn parse_loop(&mut self) -> Option<LoopStmt> {
+        let start: BriseContext = self.state.pop_front().unwrap().into();
+        let body = self.parse_block()?;
+        let context = start.to(body.context());
+
+        Some(LoopStmt::new(body, context))
+    }
+
+    /// `for i: 0..10 { ... }`
+    fn parse_for(&mut self) -> Option<ForStmt> {
+        let start: BriseContext = self.state.pop_front().unwrap().into();
+        let variable = self.identifier()?;
+
+        if !self.state.next_is(TokenKind::Colon) {
+            self.state.unexpected();
+            return None;
+        }
+        self.state.pop_front();
+        let iterable = self.parse_expr();
+        let body = self.parse_block()?;
+        let context = start.to(body.context());
+
+        Some(ForStmt::new(variable, iterable, body, context))
+    }
+
+    /// `return a;` or `return;`
+    fn parse_return(&mut self) -> Option<ReturnStmt> {
+        let start: BriseContext = self.state.pop_front().unwrap().into();
+
+        let value = if self.state.next_is(TokenKind::Semicolon) {
+            None
+        } else {
+            Some(self.parse_expr())
+        };
+        let context = self.semicolon(&start)?;
+
+        Some(ReturnStmt::new(value, context))
+    }
+
+    /// `fn add(a, b) { ... }`, a trailing comma is allowed after the last parameter
+    fn parse_fn(&mut self, doc: Vec<RawString>) -> Option<FnDecl> {
+        let start: BriseContext = self.state.pop_front().unwrap().into();
+        let name = self.identifier()?;
+
+        if !self.state.next_is(TokenKind::LeftParen) {
+            self.state.unexpected();
+            return None;
+        }
+        let left_paren = self.state.pop_front().unwrap();
+        let mut params = vec![];
+
+        while !self.state.next_is(TokenKind::RightParen) {
+            if self.state.input.is_empty() {
+                self.error(
+                    StmtErrorVariant::UnclosedParameters,
+                    left_paren.context().clone(),
+                );
+                return None;
+            }
+            params.push(self.identifier()?);
+
+            if self.state.next_is(TokenKind::Comma) {
+                self.state.pop_front();
+            } else if !self.state.next_is(TokenKind::RightParen) && !self.state.input.is_empty() {
+                self.state.unexpected();
+                return None;
+            }
+        }
+        self.state.pop_front();
+        let body = self.parse_block()?;
+        let context = start.to(body.context());
+
+        Some(FnDecl::new(name, params, body, doc, context))
+    }
+
+    /// The errors of the expression are kept, the statement goes on after it
+    fn parse_expr(&mut self) -> Expr {
+        ExprParser::parse_after(&mut self.state)
+    }
+
+    fn identifier(&mut self) -> Option<Identifier> {
+        if self.state.next_is(TokenKind::Identifier) {
+            let (variant, context) = self.state.pop_front().unwrap().into_parts();
+            let TokenVariant::Identifier(identity) = variant else {
+                unreachable!()
+            };
+
+            return Some(Identifier::new(identity, context));
+        }
+
+        self.state.unexpected();
+        None
+    }
+
+    /// Consumes the `;` ending a statement, gives the context of the whole statement.
+    /// A missing `;` is reported without failing the statement, unless it already
+    /// has an error.
+    fn semicolon(&mut self, start: &BriseContext) -> Option<BriseContext> {
+        if self.state.next_is(TokenKind::Semicolon) {
+            let semicolon = self.state.pop_front().unwrap();
+
+            return Some(start.to(semicolon.context()));
+        }
+        if self.state.recovering {
+            return None;
+        }
+
+        self.state.unexpected();
+        self.state.recovering = false;
+        Some(start.to(&self.state.last_context))
+    }
+
+    /// Only the first error of a statement is reported, the others follow from it
+    fn error(&mut self, variant: StmtErrorVariant, context: BriseContext) {
+        self.state.error(StmtError::new(variant, context).into());
+    }
+}
+
+impl SyntaxError for ParserError {
+    fn expected(expected: TokenSet, found: Found, context: BriseContext) -> Self {
+        StmtError::new(StmtErrorVariant::Expected { expected, found }, context).into()
+    }
+}
+
+/// Keywords starting a statement, where the parser synchronizes after an error
+const STMT_KEYWORDS: TokenSet = TokenSet::new(&[
+    TokenKind::Let,
+    TokenKind::If,
+    TokenKind::While,
+    TokenKind::Loop,
+    TokenKind::For,
+    TokenKind::Break,
+    TokenKind::Continue,
+    TokenKind::Return,
+    TokenKind::Fn,
+]);
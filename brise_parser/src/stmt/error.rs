@@ -0,0 +1,45 @@
+use brise_token::{BriseContext, SourceMap, TokenSet};
+use thiserror::Error;
+
+use crate::error::Found;
+
+#[derive(Debug, PartialEq, Error)]
+pub enum StmtErrorVariant {
+    /// `expected` gathers every kind of token the statement could go on with
+    #[error("Expected {expected}, found {found}")]
+    Expected { expected: TokenSet, found: Found },
+    #[error("A block was started here but was never closed")]
+    UnclosedBlock,
+    #[error("A parameter list was started here but was never closed")]
+    UnclosedParameters,
+}
+
+#[derive(Debug, Error)]
+#[error("{context} {variant}")]
+pub struct StmtError {
+    variant: StmtErrorVariant,
+    context: BriseContext,
+}
+
+impl StmtError {
+    pub fn new(variant: StmtErrorVariant, context: BriseContext) -> Self {
+        Self { variant, context }
+    }
+
+    pub fn variant(&self) -> &StmtErrorVariant {
+        &self.variant
+    }
+
+    pub fn context(&self) -> &BriseContext {
+        &self.context
+    }
+
+    pub fn into_parts(self) -> (StmtErrorVariant, BriseContext) {
+        (self.variant, self.context)
+    }
+
+    /// Error message along with the source line where the error happened
+    pub fn render(&self, source_map: &SourceMap) -> String {
+        source_map.render(&self.context, &self.variant)
+    }
+}
@@ -0,0 +1,419 @@
+use std::collections::VecDeque;
+
+use brise_syntax_tree::{
+    expr::ExprVariant,
+    program::Program,
+    stmt::{if_stmt::ElseBranch, Stmt, StmtVariant},
+};
+use brise_token::{SourceMap, Span, TokenKind};
+
+use super::{error::StmtErrorVariant, ParsedProgram, StmtParser};
+use crate::{
+    error::{Found, ParserError},
+    test_support::{after_operand, expected, expected_after_operand, found},
+    tokens::TokenParser,
+};
+
+fn parse_program(input: &str) -> Result<Program, ParserError> {
+    StmtParser::parse_program(&mut SourceMap::default(), input.into())
+}
+
+fn stmt_error(input: &str) -> (StmtErrorVariant, Span) {
+    match parse_program(input) {
+        Err(ParserError::Stmt(error)) => {
+            let (variant, context) = error.into_parts();
+            (variant, context.span())
+        }
+        result => panic!("expected a statement error for {input}, got {result:?}"),
+    }
+}
+
+/// Names the variant of every statement, nested ones in brackets
+fn outline(stmts: &[Stmt]) -> String {
+    stmts
+        .iter()
+        .map(|stmt| match stmt.variant() {
+            StmtVariant::Let(_) => "let".into(),
+            StmtVariant::Expr(_) => "expr".into(),
+            StmtVariant::Block(block) => format!("{{{}}}", outline(block.stmts())),
+            StmtVariant::If(stmt) => {
+                let else_branch = match stmt.else_branch() {
+                    Some(ElseBranch::Block(block)) => {
+                        format!(" else {{{}}}", outline(block.stmts()))
+                    }
+                    Some(ElseBranch::If(_)) => " else if".into(),
+                    None => String::new(),
+                };
+                format!(
+                    "if {{{}}}{else_branch}",
+                    outline(stmt.then_branch().stmts())
+                )
+            }
+            StmtVariant::While(stmt) => format!("while {{{}}}", outline(stmt.body().stmts())),
+            StmtVariant::Loop(stmt) => format!("loop {{{}}}", outline(stmt.body().stmts())),
+            StmtVariant::For(stmt) => format!("for {{{}}}", outline(stmt.body().stmts())),
+            StmtVariant::Break(_) => "break".into(),
+            StmtVariant::Continue(_) => "continue".into(),
+            StmtVariant::Return(_) => "return".into(),
+            StmtVariant::FnDecl(decl) => format!("fn {{{}}}", outline(decl.body().stmts())),
+        })
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+#[test]
+fn parse_every_statement() {
+    let input = "let a = 1;
+        let b;
+        fn add(x, y,) { return x + y; }
+        { a += 2; }
+        if a { loop { break; } } else if b { continue; } else { return; }
+        while a < 10 { a += 1; }
+        for i: 0..10 { b = i; }";
+
+    let program = parse_program(input).unwrap();
+
+    assert_eq!(
+        "let let fn {return} {expr} if {loop {break}} else if while {expr} for {expr}",
+        outline(program.stmts())
+    );
+    assert_eq!(Span::new(0, input.len()), program.span());
+}
+
+#[test]
+fn statement_contexts() {
+    let program = parse_program("let a = 1 + 2;\nif a { b; } else { c; }").unwrap();
+
+    assert_eq!(Span::new(0, 14), program.stmts()[0].span());
+    assert_eq!(Span::new(15, 38), program.stmts()[1].span());
+
+    let StmtVariant::Let(stmt) = program.stmts()[0].variant() else {
+        panic!("expected a let statement");
+    };
+    assert_eq!("a", stmt.name().identity().as_str());
+    assert_eq!(Span::new(8, 13), stmt.value().unwrap().span());
+}
+
+#[test]
+fn doc_comments_are_kept_on_functions() {
+    let program = parse_program("/// a\n/// b\nfn f() {}\n/// dropped\nlet c;\n/// end").unwrap();
+
+    let StmtVariant::FnDecl(decl) = program.stmts()[0].variant() else {
+        panic!("expected a function");
+    };
+    assert_eq!(
+        vec![" a", " b"],
+        decl.doc()
+            .iter()
+            .map(|doc| doc.as_str())
+            .collect::<Vec<_>>()
+    );
+    assert!(decl.params().is_empty());
+    assert_eq!(2, program.stmts().len());
+}
+
+#[test]
+fn statement_errors() {
+    let inputs = [
+        (
+            "a = 1",
+            expected_after_operand(&[TokenKind::Semicolon], Found::EndOfInput),
+            4..5,
+        ),
+        (
+            "let 1;",
+            expected(&[TokenKind::Identifier], found("1")),
+            4..5,
+        ),
+        (
+            "while a;",
+            expected_after_operand(&[TokenKind::LeftBrace], found(";")),
+            7..8,
+        ),
+        ("if a { b;", StmtErrorVariant::UnclosedBlock, 5..6),
+        (
+            "for i 0..2 {}",
+            expected(&[TokenKind::Colon], found("0")),
+            6..7,
+        ),
+        (
+            "fn f {}",
+            expected(&[TokenKind::LeftParen], found("{")),
+            5..6,
+        ),
+        (
+            "fn f(a b) {}",
+            expected(&[TokenKind::Comma, TokenKind::RightParen], found("b")),
+            7..8,
+        ),
+        ("fn f(a, b", StmtErrorVariant::UnclosedParameters, 4..5),
+        (
+            "fn f(1) {}",
+            expected(&[TokenKind::RightParen, TokenKind::Identifier], found("1")),
+            5..6,
+        ),
+        (
+            "loop",
+            expected(&[TokenKind::LeftBrace], Found::EndOfInput),
+            0..4,
+        ),
+    ];
+
+    for (input, variant, span) in inputs {
+        assert_eq!((variant, Span::from(span)), stmt_error(input), "{input}");
+    }
+}
+
+#[test]
+fn lexing_and_expression_errors_are_unified() {
+    assert!(matches!(
+        parse_program("let a = #;"),
+        Err(ParserError::Parsing(_))
+    ));
+    assert!(matches!(
+        parse_program("let a = (1;"),
+        Err(ParserError::Expr(_))
+    ));
+}
+
+#[test]
+fn parse_one_statement() {
+    let mut tokens: VecDeque<_> =
+        TokenParser::parse_deque(&mut SourceMap::default(), "break; b;".into()).unwrap();
+
+    let stmt = StmtParser::parse(&mut tokens).unwrap();
+
+    assert!(matches!(stmt.variant(), StmtVariant::Break(_)));
+    assert_eq!(2, tokens.len());
+}
+
+#[test]
+fn program_serde_round_trip() {
+    let program = parse_program("fn f(a) { if a { return 1.5; } }").unwrap();
+
+    let json = serde_json::to_value(&program).unwrap();
+    let read: Program = serde_json::from_value(json.clone()).unwrap();
+
+    assert_eq!(program, read);
+    assert!(json["stmts"][0]["FnDecl"]["body"]["stmts"][0]["If"]["else_branch"].is_null());
+}
+
+fn parse_partial(input: &str) -> ParsedProgram {
+    StmtParser::parse_program_partial(&mut SourceMap::default(), input.into())
+}
+
+fn rendered_errors(parsed: &ParsedProgram) -> Vec<String> {
+    parsed
+        .errors()
+        .iter()
+        .map(|error| match error {
+            ParserError::Expr(error) => error.variant().to_string(),
+            ParserError::Stmt(error) => error.variant().to_string(),
+            error => error.to_string(),
+        })
+        .collect()
+}
+
+#[test]
+fn every_statement_error_is_reported() {
+    let parsed = parse_partial("let = 1;\nlet a = );\nwhile a b {}\nlet c = 2;\n}\nf(c);");
+
+    assert_eq!(
+        vec![
+            "Expected an identifier, found `=`",
+            "Expected an expression, found `)`",
+            &format!(
+                "Expected {}, found `b`",
+                after_operand(&[TokenKind::LeftBrace])
+            ),
+            "Expected an expression, found `}`",
+        ],
+        rendered_errors(&parsed)
+    );
+    assert_eq!(
+        "expr expr expr let expr expr",
+        outline(parsed.program().stmts())
+    );
+}
+
+#[test]
+fn failed_statements_become_error_expressions() {
+    let parsed = parse_partial("let = 1; b;");
+    let stmts = parsed.program().stmts();
+
+    let StmtVariant::Expr(expr_stmt) = stmts[0].variant() else {
+        panic!("expected a placeholder, got {:?}", stmts[0]);
+    };
+    assert!(matches!(expr_stmt.expr().variant(), ExprVariant::Error(_)));
+    assert_eq!(Span::new(0, 8), stmts[0].span());
+    assert_eq!(Span::new(9, 11), stmts[1].span());
+}
+
+#[test]
+fn recovery_inside_blocks() {
+    let parsed = parse_partial("fn f() { let a = 1 + ; a; }\nfn g() { return }");
+
+    assert_eq!(
+        vec![
+            "Expected an expression, found `;`",
+            "Expected an expression or `;`, found `}`",
+        ],
+        rendered_errors(&parsed)
+    );
+    assert_eq!("fn {let expr} fn {expr}", outline(parsed.program().stmts()));
+}
+
+#[test]
+fn lexing_errors_are_reported_in_order() {
+    let parsed = parse_partial("let a = );\nlet b = #;");
+    let errors = parsed.errors();
+
+    assert_eq!(2, errors.len());
+    assert!(matches!(errors[0], ParserError::Expr(_)));
+    assert!(matches!(errors[1], ParserError::Parsing(_)));
+}
+
+#[test]
+fn missing_semicolon_keeps_the_statement() {
+    let parsed = parse_partial("let a = 1\nlet b = 2;");
+
+    assert_eq!(
+        vec![format!(
+            "Expected {}, found `let`",
+            after_operand(&[TokenKind::Semicolon])
+        )],
+        rendered_errors(&parsed)
+    );
+    assert_eq!("let let", outline(parsed.program().stmts()));
+}
+
+#[test]
+fn empty_input_has_no_error() {
+    let parsed = parse_partial("");
+
+    assert!(parsed.errors().is_empty());
+    assert!(parsed.program().stmts().is_empty());
+}
+
+#[test]
+fn expected_tokens_are_accumulated() {
+    let inputs = [
+        ("let a b;", "Expected one of `;`, `=`, found `b`".into()),
+        (
+            "if a {} else b",
+            "Expected one of `{`, `if`, found `b`".into(),
+        ),
+        ("fn f(a b) {}", "Expected one of `)`, `,`, found `b`".into()),
+        (
+            "fn f(1) {}",
+            "Expected one of `)`, an identifier, found `1`".into(),
+        ),
+        (
+            "{ return }",
+            "Expected an expression or `;`, found `}`".into(),
+        ),
+        ("f(;", "Expected an expression or `)`, found `;`".into()),
+        (
+            "let a = 1 2;",
+            format!(
+                "Expected {}, found `2`",
+                after_operand(&[TokenKind::Semicolon])
+            ),
+        ),
+        (
+            "f(a b);",
+            format!(
+                "Expected {}, found `b`",
+                after_operand(&[TokenKind::RightParen, TokenKind::Comma])
+            ),
+        ),
+    ];
+
+    for (input, message) in inputs {
+        assert_eq!(
+            vec![message],
+            rendered_errors(&parse_partial(input)),
+            "{input}"
+        );
+    }
+}
+
+#[test]
+fn chained_ranges_are_reported_once() {
+    let parsed = parse_partial("a..b..c;");
+
+    assert_eq!(
+        vec!["Range operators can't be chained"],
+        rendered_errors(&parsed)
+    );
+    assert_eq!("expr", outline(parsed.program().stmts()));
+}
+
+#[test]
+fn bad_calls_and_indexes_are_reported_once() {
+    let inputs = [
+        (
+            "f(;); x;",
+            "Expected an expression or `)`, found `;`".into(),
+        ),
+        ("a[;]; x;", "Expected an expression, found `;`".into()),
+        (
+            "f(a b c); x;",
+            format!(
+                "Expected {}, found `b`",
+                after_operand(&[TokenKind::RightParen, TokenKind::Comma])
+            ),
+        ),
+        (
+            "a[0 1]; x;",
+            format!(
+                "Expected {}, found `1`",
+                after_operand(&[TokenKind::RightBracket])
+            ),
+        ),
+        (
+            "f(a, (b c)); x;",
+            format!(
+                "Expected {}, found `c`",
+                after_operand(&[TokenKind::RightParen])
+            ),
+        ),
+    ];
+
+    for (input, message) in inputs {
+        let parsed = parse_partial(input);
+
+        assert_eq!(vec![message], rendered_errors(&parsed), "{input}");
+        assert_eq!("expr expr", outline(parsed.program().stmts()), "{input}");
+    }
+}
+
+#[test]
+fn no_postfix_after_a_token_which_is_not_an_expression() {
+    let parsed = parse_partial("let a = [1, 2]; x;");
+
+    assert_eq!(
+        vec!["Expected an expression, found `[`"],
+        rendered_errors(&parsed)
+    );
+    assert_eq!("expr expr", outline(parsed.program().stmts()));
+}
+
+#[test]
+fn errors_following_a_lexing_error_are_not_reported() {
+    let inputs = [
+        (
+            "$\"{a\";",
+            vec![
+                "[1:3] Missing `}` to close the interpolation started here",
+                "[1:5] Missing end of string `\"`, string started here but never end",
+            ],
+        ),
+        ("a @ b; c;", vec!["[1:3] Unexpected character: @"]),
+        ("let a = @ 1; c;", vec!["[1:9] Unexpected character: @"]),
+    ];
+
+    for (input, messages) in inputs {
+        assert_eq!(messages, rendered_errors(&parse_partial(input)), "{input}");
+    }
+}
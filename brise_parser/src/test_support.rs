@@ -0,0 +1,45 @@
+//! Helpers shared by the tests of the statement and expression parsers
+
+use brise_token::{TokenKind, TokenSet};
+
+use crate::{
+    error::Found,
+    expr::{error::ExprErrorVariant, OPERATOR_KINDS, POSTFIX_KINDS},
+    stmt::error::StmtErrorVariant,
+};
+
+/// Error variants reporting a token met in place of the expected kinds
+pub trait ExpectedVariant {
+    fn expected(expected: TokenSet, found: Found) -> Self;
+}
+
+impl ExpectedVariant for ExprErrorVariant {
+    fn expected(expected: TokenSet, found: Found) -> Self {
+        Self::Expected { expected, found }
+    }
+}
+
+impl ExpectedVariant for StmtErrorVariant {
+    fn expected(expected: TokenSet, found: Found) -> Self {
+        Self::Expected { expected, found }
+    }
+}
+
+pub fn found(text: &str) -> Found {
+    Found::Token(text.into())
+}
+
+pub fn expected<V: ExpectedVariant>(kinds: &[TokenKind], found: Found) -> V {
+    V::expected(TokenSet::new(kinds), found)
+}
+
+/// After an operand, the expression could also have gone on with an operator or a postfix
+pub fn after_operand(kinds: &[TokenKind]) -> TokenSet {
+    TokenSet::new(kinds)
+        .union(OPERATOR_KINDS)
+        .union(POSTFIX_KINDS)
+}
+
+pub fn expected_after_operand<V: ExpectedVariant>(kinds: &[TokenKind], found: Found) -> V {
+    V::expected(after_operand(kinds), found)
+}
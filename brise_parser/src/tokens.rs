@@ -1,140 +1,425 @@
-use std::{collections::VecDeque, num::NonZeroUsize};
+use std::{
+    borrow::Cow,
+    collections::{HashMap, VecDeque},
+};
 
 use crate::error::ParserError;
 pub use brise_token;
 
-use brise_token::{BriseContext, BriseFile, Column, Line, Token, TokenVariant};
+use brise_token::{
+    BriseContext, BriseFile, Column, FileId, Line, RawString, SourceMap, Span, Token, TokenVariant,
+    Trivia, TriviaKind,
+};
+use cursor::Cursor;
 use error::{ParsingError, ParsingErrorVariant, ParsingErrors};
+use unicode_normalization::{is_nfc, UnicodeNormalization};
+use unicode_security::{confusable_detection::skeleton, MixedScript};
+use unicode_xid::UnicodeXID;
+use warning::{ParsingWarning, ParsingWarningVariant};
 
-pub mod error;
-#[cfg(test)]
-mod tests;
+/// Skipped at the start of the input, as a `#!` line following it
+const BYTE_ORDER_MARK: char = '\u{feff}';
 
-enum Collection<'a> {
-    Vec(&'a mut Vec<Token>),
-    VecDeque(&'a mut VecDeque<Token>),
+/// Every token of an input along with the diagnostics found while lexing it
+#[derive(Debug)]
+pub struct LexedTokens {
+    file: FileId,
+    tokens: Vec<Token>,
+    errors: Vec<ParsingError>,
+    warnings: Vec<ParsingWarning>,
+    /// Whether the tokens hold their trivia, see [`TokenParser::new_lossless`]
+    lossless: bool,
 }
 
-impl Collection<'_> {
-    pub fn push(&mut self, token: Token) {
-        match self {
-            Collection::Vec(vec) => vec.push(token),
-            Collection::VecDeque(vecdeque) => vecdeque.push_back(token),
-        }
+impl LexedTokens {
+    pub fn file(&self) -> FileId {
+        self.file
+    }
+
+    pub fn tokens(&self) -> &[Token] {
+        &self.tokens
+    }
+
+    pub fn errors(&self) -> &[ParsingError] {
+        &self.errors
+    }
+
+    pub fn warnings(&self) -> &[ParsingWarning] {
+        &self.warnings
     }
+
+    pub fn into_parts(self) -> (Vec<Token>, Vec<ParsingError>, Vec<ParsingWarning>) {
+        (self.tokens, self.errors, self.warnings)
+    }
+}
+
+mod cursor;
+pub mod error;
+pub mod printer;
+mod relex;
+#[cfg(test)]
+mod tests;
+pub mod warning;
+
+#[derive(Debug, Default, Clone, Copy)]
+struct Position {
+    offset: usize,
+    line: Line,
+    col: Column,
 }
 
+/// Streaming lexer, each call to [`Iterator::next`] lexes the next token of the input
 #[derive(Debug)]
-pub struct TokenParser {
-    file: Option<BriseFile>,
+pub struct TokenParser<'a> {
+    file: FileId,
     line: Line,
     col: Column,
-    current: usize,
-    input: String,
+    cursor: Cursor<'a>,
+    /// Start of the token being lexed
+    token_start: Position,
+    errors: Vec<ParsingError>,
+    warnings: Vec<ParsingWarning>,
+    /// Skeleton of every identifier met, to detect confusable identifiers
+    skeletons: HashMap<String, RawString>,
+    /// Keeps the whitespaces and comments around tokens, see [`Self::new_lossless`]
+    lossless: bool,
+    reached_end: bool,
 }
 
-impl TokenParser {
-    pub fn parse_file(file: impl Into<BriseFile>) -> Result<Vec<Token>, ParserError> {
-        let file = file.into();
-        let input =
-            Self::read_file(&file).map_err(|e| ParserError::FailedToReadFile(file.clone(), e))?;
+impl TokenParser<'_> {
+    pub fn parse_file(
+        source_map: &mut SourceMap,
+        file: impl Into<BriseFile>,
+    ) -> Result<Vec<Token>, ParserError> {
+        let file = Self::load_file(source_map, file)?;
 
-        let mut tokens = vec![];
-        let mut collection = Collection::Vec(&mut tokens);
+        Ok(TokenParser::new(file, source_map.text(file)).collect_tokens()?)
+    }
 
-        Self::new(Some(file), input).parse_input(&mut collection)?;
+    pub fn parse(source_map: &mut SourceMap, input: String) -> Result<Vec<Token>, ParserError> {
+        let file = source_map.add_snippet(input);
 
-        Ok(tokens)
+        Ok(TokenParser::new(file, source_map.text(file)).collect_tokens()?)
     }
 
-    pub fn parse(input: String) -> Result<Vec<Token>, ParserError> {
-        let mut tokens = vec![];
-        let mut collection = Collection::Vec(&mut tokens);
-        Self::new(None, input)
-            .parse_input(&mut collection)?;
+    /// Lexes the whole input even when it is invalid, see [`TokenParser::finish`]
+    pub fn lex(source_map: &mut SourceMap, input: String) -> LexedTokens {
+        let file = source_map.add_snippet(input);
 
-        Ok(tokens)
+        TokenParser::new(file, source_map.text(file)).finish()
     }
 
-    pub fn parse_file_deque(file: impl Into<BriseFile>) -> Result<VecDeque<Token>, ParserError> {
-        let file = file.into();
-        let input =
-            Self::read_file(&file).map_err(|e| ParserError::FailedToReadFile(file.clone(), e))?;
+    pub fn lex_file(
+        source_map: &mut SourceMap,
+        file: impl Into<BriseFile>,
+    ) -> Result<LexedTokens, ParserError> {
+        let file = Self::load_file(source_map, file)?;
 
-        let mut tokens = VecDeque::new();
-        let mut collection = Collection::VecDeque(&mut tokens);
+        Ok(TokenParser::new(file, source_map.text(file)).finish())
+    }
 
-        Self::new(Some(file), input).parse_input(&mut collection)?;
+    pub fn parse_lossless(
+        source_map: &mut SourceMap,
+        input: String,
+    ) -> Result<Vec<Token>, ParserError> {
+        let file = source_map.add_snippet(input);
 
-        Ok(tokens)
+        Ok(TokenParser::new_lossless(file, source_map.text(file)).collect_tokens()?)
     }
 
-    pub fn parse_deque(input: String) -> Result<VecDeque<Token>, ParserError> {
-        let mut tokens = VecDeque::new();
-        let mut collection = Collection::VecDeque(&mut tokens);
-        Self::new(None, input)
-            .parse_input(&mut collection)?;
+    pub fn parse_file_deque(
+        source_map: &mut SourceMap,
+        file: impl Into<BriseFile>,
+    ) -> Result<VecDeque<Token>, ParserError> {
+        let file = Self::load_file(source_map, file)?;
 
-        Ok(tokens)
+        Ok(TokenParser::new(file, source_map.text(file)).collect_tokens()?)
     }
 
-    fn read_file(file_path: &BriseFile) -> Result<String, std::io::Error> {
-        std::fs::read_to_string(file_path.as_path())
+    pub fn parse_deque(
+        source_map: &mut SourceMap,
+        input: String,
+    ) -> Result<VecDeque<Token>, ParserError> {
+        let file = source_map.add_snippet(input);
+
+        Ok(TokenParser::new(file, source_map.text(file)).collect_tokens()?)
+    }
+
+    fn load_file(
+        source_map: &mut SourceMap,
+        file: impl Into<BriseFile>,
+    ) -> Result<FileId, ParserError> {
+        let file = file.into();
+
+        source_map
+            .load_file(file.clone())
+            .map_err(|e| ParserError::FailedToReadFile(file, e))
     }
+}
 
-    fn new(file: Option<BriseFile>, input: String) -> Self {
+impl<'a> TokenParser<'a> {
+    pub fn new(file: FileId, input: &'a str) -> Self {
         Self {
             file,
             line: Line::default(),
             col: Column::default(),
-            current: 0,
-            input,
+            cursor: Cursor::new(input),
+            token_start: Position::default(),
+            errors: vec![],
+            warnings: vec![],
+            skeletons: HashMap::new(),
+            lossless: false,
+            reached_end: false,
         }
     }
 
-    fn parse_input(&mut self, tokens: &mut Collection) -> Result<(), ParsingErrors> {
-        let mut errors = vec![];
-        let input_len = self.input.chars().count();
+    /// Lexer keeping every whitespace and comment as trivia of the tokens, and ending
+    /// the input with an `EndOfFile` token. Concatenating the [`Token::full_span`]
+    /// of every token gives back the whole input.
+    pub fn new_lossless(file: FileId, input: &'a str) -> Self {
+        Self {
+            lossless: true,
+            ..Self::new(file, input)
+        }
+    }
 
-        while self.current < input_len {
-            match self.parse_next_token() {
-                Ok(token) => tokens.push(token),
-                Err(e) => errors.push(e),
-            }
-            self.current += 1;
+    /// Lexer starting at a token boundary in the middle of the input
+    fn resume(file: FileId, input: &'a str, start: Position, lossless: bool) -> Self {
+        Self {
+            line: start.line,
+            col: start.col,
+            cursor: Cursor::at(input, start.offset),
+            lossless,
+            ..Self::new(file, input)
+        }
+    }
+
+    /// Errors found so far, each one matches an `Error` token of the stream
+    pub fn errors(&self) -> &[ParsingError] {
+        &self.errors
+    }
+
+    /// Warnings found so far, the input is lexed lazily so the list grows while iterating
+    pub fn warnings(&self) -> &[ParsingWarning] {
+        &self.warnings
+    }
+
+    /// Lexes the rest of the input, invalid input is kept as `Error` tokens
+    pub fn finish(mut self) -> LexedTokens {
+        let tokens = self.by_ref().collect();
+
+        LexedTokens {
+            file: self.file,
+            tokens,
+            errors: self.errors,
+            warnings: self.warnings,
+            lossless: self.lossless,
         }
+    }
 
-        if !errors.is_empty() {
-            Err(errors.into())
+    /// Lexes the whole input, every error is reported and not only the first one
+    fn collect_tokens<C: Default + Extend<Token>>(mut self) -> Result<C, ParsingErrors> {
+        let mut tokens = C::default();
+        tokens.extend(self.by_ref());
+
+        if !self.errors.is_empty() {
+            Err(self.errors.into())
         } else {
-            Ok(())
+            Ok(tokens)
+        }
+    }
+
+    /// Trivia before a token, only kept in lossless mode
+    fn leading_trivia(&mut self) -> Vec<Trivia> {
+        self.trivia(false)
+    }
+
+    /// Trivia after a token up to the end of its line, only kept in lossless mode
+    fn trailing_trivia(&mut self) -> Vec<Trivia> {
+        self.trivia(true)
+    }
+
+    fn trivia(&mut self, stop_at_newline: bool) -> Vec<Trivia> {
+        let mut trivia = vec![];
+
+        while let Some(piece) = self.next_trivia(stop_at_newline) {
+            if self.lossless {
+                trivia.push(piece);
+            }
         }
+
+        trivia
     }
 
-    fn parse_next_token(&mut self) -> Result<Token, ParsingError> {
-        // We can unwrap here, because a check is made by the loop in parse
-        let current_token = self.input.chars().nth(self.current).unwrap();
-        match current_token {
-            '\n' => {
-                self.line += 1;
-                self.current += 1;
-                self.parse_next_token()
+    /// Skips the next whitespaces or comment, doc comments are not trivia as they are tokens.
+    /// An unterminated block comment is not trivia either, it is lexed as an `Error` token.
+    fn next_trivia(&mut self, stop_at_newline: bool) -> Option<Trivia> {
+        let start = self.cursor.offset();
+        let rest = self.cursor.rest();
+
+        let kind = if start == 0 && rest.starts_with(BYTE_ORDER_MARK) {
+            // Editors don't show the mark, it takes no column
+            self.cursor.bump();
+            TriviaKind::ByteOrderMark
+        } else if rest.starts_with("#!") && matches!(self.cursor.slice(0, start), "" | "\u{feff}") {
+            self.bump_line();
+            TriviaKind::Shebang
+        } else if rest.starts_with('\n') || rest.starts_with("\r\n") {
+            if stop_at_newline {
+                return None;
             }
-            '\t' | '\r' | ' ' => {
-                self.col += 1;
-                self.current += 1;
-                self.parse_next_token()
+            self.bump_if('\r');
+            self.bump();
+            TriviaKind::Newline
+        } else if rest.starts_with(['\t', ' ']) || Self::is_lone_carriage_return(rest) {
+            while self
+                .cursor
+                .peek()
+                .is_some_and(|ch| matches!(ch, '\t' | ' '))
+                || Self::is_lone_carriage_return(self.cursor.rest())
+            {
+                self.bump();
             }
-            _ => {
-                let result = self.parse_token(current_token);
-                self.col += 1;
-                result
+            TriviaKind::Whitespace
+        } else if rest.starts_with("//") && !Self::is_doc_comment(rest) {
+            self.bump_line();
+            TriviaKind::LineComment
+        } else if rest.starts_with("/*") {
+            let snapshot = (self.cursor.clone(), self.line, self.col);
+
+            if self.block_comment().is_err() {
+                (self.cursor, self.line, self.col) = snapshot;
+                return None;
+            }
+            TriviaKind::BlockComment
+        } else {
+            return None;
+        };
+
+        Some(Trivia::new(kind, Span::new(start, self.cursor.offset())))
+    }
+
+    /// A `\r` is only part of a newline when it is followed by `\n`
+    fn is_lone_carriage_return(input: &str) -> bool {
+        input.starts_with('\r') && !input.starts_with("\r\n")
+    }
+
+    /// `///` starts a doc comment, but `////` is a regular comment
+    fn is_doc_comment(input: &str) -> bool {
+        input.starts_with("///") && !input.starts_with("////")
+    }
+
+    /// Block comments can be nested: `/* a /* b */ c */`
+    fn block_comment(&mut self) -> Result<(), ParsingError> {
+        let start = self.current_position();
+        let mut depth = 0usize;
+
+        loop {
+            let rest = self.cursor.rest();
+
+            if rest.starts_with("/*") {
+                self.bump();
+                self.bump();
+                depth += 1;
+            } else if rest.starts_with("*/") {
+                self.bump();
+                self.bump();
+                depth -= 1;
+
+                if depth == 0 {
+                    return Ok(());
+                }
+            } else if self.bump().is_none() {
+                return Err(ParsingError::new(
+                    ParsingErrorVariant::UnterminatedBlockComment,
+                    self.context_from(start),
+                ));
             }
         }
     }
 
+    /// Consumes the next char, keeping the line and the column up to date. Columns are
+    /// counted in chars, as [`brise_token::ColumnUnit::Char`] columns of a `LineIndex`.
+    /// The `\r` of a `\r\n` is part of the line ending and takes no column.
+    fn bump(&mut self) -> Option<char> {
+        let ch = self.cursor.bump()?;
+
+        if ch == '\n' {
+            self.line += 1;
+            self.col = Column::default();
+        } else if ch != '\r' || self.cursor.peek() != Some('\n') {
+            self.col += 1;
+        }
+
+        Some(ch)
+    }
+
+    fn bump_if(&mut self, c: char) -> bool {
+        if self.cursor.peek() == Some(c) {
+            self.bump();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn bump_while(&mut self, predicate: impl Fn(char) -> bool) {
+        while self.cursor.peek().is_some_and(&predicate) {
+            self.bump();
+        }
+    }
+
+    /// Consumes the rest of the line, up to its line ending
+    fn bump_line(&mut self) {
+        while self.cursor.peek().is_some_and(|ch| ch != '\n')
+            && !self.cursor.rest().starts_with("\r\n")
+        {
+            self.bump();
+        }
+    }
+
+    fn lex_token(&mut self) -> Option<Token> {
+        let leading_trivia = self.leading_trivia();
+        self.token_start = self.current_position();
+
+        let result = if self.cursor.rest().starts_with("/*") {
+            // Terminated block comments have been skipped as trivia
+            Err(self
+                .block_comment()
+                .expect_err("the block comment is unterminated"))
+        } else {
+            let Some(token) = self.bump() else {
+                return self.end_of_file(leading_trivia);
+            };
+            self.parse_token(token)
+        };
+
+        let token = result.unwrap_or_else(|error| self.error_token(error));
+        let trailing_trivia = self.trailing_trivia();
+
+        Some(token.with_trivia(leading_trivia, trailing_trivia))
+    }
+
+    /// Records the error and covers the text lexed for the current token
+    fn error_token(&mut self, error: ParsingError) -> Token {
+        self.errors.push(error);
+
+        Token::new(TokenVariant::Error, self.compute_context())
+    }
+
+    /// In lossless mode, the trivia ending the input are held by an `EndOfFile` token
+    fn end_of_file(&mut self, leading_trivia: Vec<Trivia>) -> Option<Token> {
+        if !self.lossless || self.reached_end {
+            return None;
+        }
+
+        self.reached_end = true;
+        let token = Token::new(TokenVariant::EndOfFile, self.compute_context());
+
+        Some(token.with_trivia(leading_trivia, vec![]))
+    }
+
     fn parse_token(&mut self, token: char) -> Result<Token, ParsingError> {
-        let context = self.compute_context();
         let variant = match token {
             '(' => TokenVariant::LeftParen,
             ')' => TokenVariant::RightParen,
@@ -144,28 +429,29 @@ impl TokenParser {
             ']' => TokenVariant::RightBracket,
             ';' => TokenVariant::Semicolon,
             ',' => TokenVariant::Comma,
-            ':' => TokenVariant::Colon,
-            '.' => TokenVariant::Dot,
-            '+' => TokenVariant::Plus,
-            '/' => TokenVariant::Slash,
-            '*' => TokenVariant::Star,
+            ':' => self.colon(),
+            '.' => self.dot(),
+            '+' => self.plus(),
+            '/' if self.bump_if('/') => self.doc_comment(),
+            '/' => self.slash(),
+            '*' => self.star(),
+            '%' => self.percent(),
             '?' => TokenVariant::QuestionMark,
-            '=' => TokenVariant::Equal,
+            '=' => self.equal(),
             '!' => self.bang(),
             '>' => self.greater(),
             '<' => self.less(),
             '-' => self.minus(),
-            '&' if self.next_token_matches('&') => {
-                self.current += 1;
-                TokenVariant::AmpersandAmpersand
-            }
-            '|' if self.next_token_matches('|') => {
-                self.current += 1;
-                TokenVariant::BarBar
-            }
-            '0'..='9' => self.number(token),
+            '&' if self.bump_if('&') => TokenVariant::AmpersandAmpersand,
+            '|' if self.bump_if('|') => TokenVariant::BarBar,
+            '|' if self.bump_if('>') => TokenVariant::BarGreater,
+            '0'..='9' => self.number(token)?,
+            '"' if self.cursor.rest().starts_with("\"\"") => self.multiline_string()?,
             '"' => self.string()?,
-            '_' | 'a'..='z' | 'A'..='Z' => self.identifier(),
+            'r' if matches!(self.cursor.peek(), Some('"' | '#')) => self.raw_string()?,
+            '$' if self.bump_if('"') => self.formatted_string()?,
+            '_' => self.identifier(),
+            ch if ch.is_xid_start() => self.identifier(),
             _ => {
                 return Err(ParsingError::new(
                     ParsingErrorVariant::UnexpectedCharacter(token),
@@ -174,36 +460,107 @@ impl TokenParser {
             }
         };
 
-        Ok(Token::new(variant, context))
+        Ok(Token::new(variant, self.compute_context()))
     }
 
-    fn next_token_matches(&self, c: char) -> bool {
-        self.input
-            .chars()
-            .nth(self.current + 1)
-            .is_some_and(|next_char| next_char == c)
+    fn current_position(&self) -> Position {
+        Position {
+            offset: self.cursor.offset(),
+            line: self.line,
+            col: self.col,
+        }
     }
 
+    /// Context spanning from the start of the current token to the cursor
     fn compute_context(&self) -> BriseContext {
-        BriseContext::new(self.file.clone(), self.line, self.col)
+        self.context_from(self.token_start)
+    }
+
+    fn context_from(&self, start: Position) -> BriseContext {
+        BriseContext::new(
+            self.file,
+            Span::new(start.offset, self.cursor.offset()),
+            start.line,
+            start.col,
+        )
+    }
+
+    fn colon(&mut self) -> TokenVariant {
+        if self.bump_if(':') {
+            TokenVariant::ColonColon
+        } else {
+            TokenVariant::Colon
+        }
+    }
+
+    fn dot(&mut self) -> TokenVariant {
+        if !self.bump_if('.') {
+            TokenVariant::Dot
+        } else if self.bump_if('=') {
+            TokenVariant::DotDotEqual
+        } else {
+            TokenVariant::DotDot
+        }
+    }
+
+    fn equal(&mut self) -> TokenVariant {
+        if self.bump_if('=') {
+            TokenVariant::EqualEqual
+        } else if self.bump_if('>') {
+            TokenVariant::FatArrow
+        } else {
+            TokenVariant::Equal
+        }
+    }
+
+    fn plus(&mut self) -> TokenVariant {
+        if self.bump_if('=') {
+            TokenVariant::PlusEqual
+        } else {
+            TokenVariant::Plus
+        }
     }
 
     fn minus(&mut self) -> TokenVariant {
-        if self.next_token_matches('>') {
-            self.current += 1;
-            self.col += 1;
+        if self.bump_if('>') {
             TokenVariant::RightArrow
+        } else if self.bump_if('=') {
+            TokenVariant::MinusEqual
         } else {
             TokenVariant::Minus
         }
     }
 
+    fn slash(&mut self) -> TokenVariant {
+        if self.bump_if('=') {
+            TokenVariant::SlashEqual
+        } else {
+            TokenVariant::Slash
+        }
+    }
+
+    fn star(&mut self) -> TokenVariant {
+        if self.bump_if('*') {
+            TokenVariant::StarStar
+        } else if self.bump_if('=') {
+            TokenVariant::StarEqual
+        } else {
+            TokenVariant::Star
+        }
+    }
+
+    fn percent(&mut self) -> TokenVariant {
+        if self.bump_if('=') {
+            TokenVariant::PercentEqual
+        } else {
+            TokenVariant::Percent
+        }
+    }
+
     fn bang(&mut self) -> TokenVariant {
-        if self.next_token_matches('=') {
-            self.current += 1;
+        if self.bump_if('=') {
             TokenVariant::BangEqual
-        } else if self.next_token_matches('>') {
-            self.current += 1;
+        } else if self.bump_if('>') {
             TokenVariant::BangRightChevron
         } else {
             TokenVariant::Bang
@@ -211,8 +568,7 @@ impl TokenParser {
     }
 
     fn greater(&mut self) -> TokenVariant {
-        if self.next_token_matches('=') {
-            self.current += 1;
+        if self.bump_if('=') {
             TokenVariant::GreaterEqual
         } else {
             TokenVariant::Greater
@@ -220,97 +576,433 @@ impl TokenParser {
     }
 
     fn less(&mut self) -> TokenVariant {
-        if self.next_token_matches('=') {
-            self.current += 1;
+        if self.bump_if('=') {
             TokenVariant::LessEqual
         } else {
             TokenVariant::Less
         }
     }
 
-    fn number(&mut self, first_ch: char) -> TokenVariant {
-        let mut num_str = String::from(first_ch);
-        let mut has_dot = false;
+    /// The first `//` has already been consumed, comments which are not doc comments
+    /// are skipped before reaching this point
+    fn doc_comment(&mut self) -> TokenVariant {
+        self.bump();
+        let start = self.cursor.offset();
+        self.bump_line();
+        let text = self.cursor.slice(start, self.cursor.offset());
 
-        loop {
-            self.current += 1;
-            let Some(next_ch) = self.input.chars().nth(self.current) else {
+        TokenVariant::DocComment(text.into())
+    }
+
+    /// Integer and float literals, in base 10 or with a `0x`, `0o` or `0b` prefix,
+    /// the first digit has already been consumed
+    fn number(&mut self, first_digit: char) -> Result<TokenVariant, ParsingError> {
+        let radix = match (first_digit, self.cursor.peek()) {
+            ('0', Some('x')) => 16,
+            ('0', Some('o')) => 8,
+            ('0', Some('b')) => 2,
+            _ => 10,
+        };
+
+        if radix != 10 {
+            self.bump();
+            return self.prefixed_integer(radix);
+        }
+
+        let mut is_float = false;
+        self.digits(10);
+
+        if self.cursor.peek() == Some('.')
+            && self
+                .cursor
+                .peek_second()
+                .is_some_and(|ch| ch.is_ascii_digit())
+        {
+            self.bump();
+            self.digits(10);
+            is_float = true;
+        }
+
+        if self.cursor.peek().is_some_and(|ch| matches!(ch, 'e' | 'E')) {
+            self.bump();
+            if !self.bump_if('-') {
+                self.bump_if('+');
+            }
+
+            if !self.digits(10) {
+                return Err(ParsingError::new(
+                    ParsingErrorVariant::MissingExponentDigits,
+                    self.compute_context(),
+                ));
+            }
+            is_float = true;
+        }
+
+        self.number_suffix(radix)?;
+
+        let literal = self.number_literal(self.token_start.offset);
+        if is_float {
+            // We can unwrap here, because the literal only contains valid float syntax
+            let value: f64 = literal.parse().unwrap();
+            // No literal is written as infinite, it is too large instead
+            if value.is_infinite() {
+                return Err(ParsingError::new(
+                    ParsingErrorVariant::FloatOverflow,
+                    self.compute_context(),
+                ));
+            }
+            Ok(TokenVariant::Float(value))
+        } else {
+            literal.parse().map(TokenVariant::Integer).map_err(|_| {
+                ParsingError::new(ParsingErrorVariant::IntegerOverflow, self.compute_context())
+            })
+        }
+    }
+
+    /// The prefix has already been consumed
+    fn prefixed_integer(&mut self, radix: u32) -> Result<TokenVariant, ParsingError> {
+        let digits_start = self.cursor.offset();
+        let has_digits = self.digits(radix);
+        self.number_suffix(radix)?;
+
+        if !has_digits {
+            return Err(ParsingError::new(
+                ParsingErrorVariant::MissingDigits,
+                self.compute_context(),
+            ));
+        }
+
+        i64::from_str_radix(&self.number_literal(digits_start), radix)
+            .map(TokenVariant::Integer)
+            .map_err(|_| {
+                ParsingError::new(ParsingErrorVariant::IntegerOverflow, self.compute_context())
+            })
+    }
+
+    /// Consumes digits and `_` separators, returns whether a digit was consumed
+    fn digits(&mut self, radix: u32) -> bool {
+        let mut has_digits = false;
+
+        while let Some(ch) = self.cursor.peek() {
+            if ch.is_digit(radix) {
+                has_digits = true;
+            } else if ch != '_' {
                 break;
+            }
+            self.bump();
+        }
+
+        has_digits
+    }
+
+    /// A number can't be directly followed by a letter or by a digit of a larger base
+    fn number_suffix(&mut self, radix: u32) -> Result<(), ParsingError> {
+        match self.cursor.peek() {
+            Some(digit) if digit.is_xid_continue() => {
+                self.bump_while(|ch| ch.is_xid_continue());
+
+                Err(ParsingError::new(
+                    ParsingErrorVariant::InvalidDigit { digit, radix },
+                    self.compute_context(),
+                ))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Text of the number from `start` to the cursor, without the `_` separators
+    fn number_literal(&self, start: usize) -> String {
+        self.cursor
+            .slice(start, self.cursor.offset())
+            .chars()
+            .filter(|ch| *ch != '_')
+            .collect()
+    }
+
+    /// The opening `"` has already been consumed
+    fn string(&mut self) -> Result<TokenVariant, ParsingError> {
+        let mut text = String::new();
+        let mut first_error = None;
+
+        loop {
+            let char_start = self.current_position();
+            let Some(ch) = self.bump() else {
+                return Err(ParsingError::new(
+                    ParsingErrorVariant::UnterminatedString,
+                    self.compute_context(),
+                ));
             };
 
-            match next_ch {
-                '0'..='9' => num_str.push(next_ch),
-                '.' => {
-                    if has_dot {
-                        break;
-                    } else {
-                        has_dot = true;
-                        num_str.push(next_ch)
+            match ch {
+                '"' => break,
+                '\\' => match self.escape(char_start) {
+                    Ok(escaped) => text.push(escaped),
+                    Err(error) => {
+                        first_error.get_or_insert(error);
                     }
-                }
-                _ => break,
+                },
+                _ => text.push(ch),
             }
         }
 
-        self.col += num_str.len();
-        // We can unwrap here, because the parse should never fail
-        TokenVariant::Number(num_str.parse().unwrap())
+        match first_error {
+            Some(error) => Err(error),
+            None => Ok(TokenVariant::String(text.into())),
+        }
     }
 
-    fn string(&mut self) -> Result<TokenVariant, ParsingError> {
-        let Some(str_length) = self
-            .input
-            .chars()
-            .skip(self.current + 1)
-            .position(|ch| ch == '"')
-        else {
+    /// `r"..."` or `r#"..."#` with any number of `#`, escapes are not decoded.
+    /// The `r` has already been consumed
+    fn raw_string(&mut self) -> Result<TokenVariant, ParsingError> {
+        let hashes_start = self.cursor.offset();
+        self.bump_while(|ch| ch == '#');
+        let hashes = self.cursor.slice(hashes_start, self.cursor.offset());
+
+        if !self.bump_if('"') {
             return Err(ParsingError::new(
-                ParsingErrorVariant::UnterminatedString,
+                ParsingErrorVariant::MissingRawStringQuote,
                 self.compute_context(),
             ));
+        }
+
+        let text_start = self.cursor.offset();
+        loop {
+            let text_end = self.cursor.offset();
+
+            match self.bump() {
+                Some('"') if self.cursor.rest().starts_with(hashes) => {
+                    for _ in 0..hashes.len() {
+                        self.bump();
+                    }
+
+                    let text = self.cursor.slice(text_start, text_end);
+                    return Ok(TokenVariant::String(text.into()));
+                }
+                Some(_) => {}
+                None => {
+                    return Err(ParsingError::new(
+                        ParsingErrorVariant::UnterminatedString,
+                        self.compute_context(),
+                    ))
+                }
+            }
+        }
+    }
+
+    /// `"""` strings spanning several lines. The line break after the opening quotes,
+    /// the line of the closing quotes when it is blank, and the indentation common
+    /// to every non-blank line are removed. The first `"` has already been consumed
+    fn multiline_string(&mut self) -> Result<TokenVariant, ParsingError> {
+        self.bump();
+        self.bump();
+
+        let mut lines = vec![StringLine::default()];
+        let mut first_error = None;
+
+        while !self.cursor.rest().starts_with("\"\"\"") {
+            let char_start = self.current_position();
+            let Some(ch) = self.bump() else {
+                return Err(ParsingError::new(
+                    ParsingErrorVariant::UnterminatedString,
+                    self.compute_context(),
+                ));
+            };
+
+            match ch {
+                '\n' => lines.push(StringLine::default()),
+                '\r' if self.cursor.peek() == Some('\n') => {}
+                '\\' => match self.escape(char_start) {
+                    // We can unwrap here, because there is always a line
+                    Ok(escaped) => lines.last_mut().unwrap().push(escaped, false),
+                    Err(error) => {
+                        first_error.get_or_insert(error);
+                    }
+                },
+                _ => lines.last_mut().unwrap().push(ch, true),
+            }
+        }
+        for _ in 0..3 {
+            self.bump();
+        }
+
+        if let Some(error) = first_error {
+            return Err(error);
+        }
+
+        Ok(TokenVariant::String(StringLine::join(lines).into()))
+    }
+
+    /// Decodes an escape sequence, the `\` starting at `start` has already been consumed
+    fn escape(&mut self, start: Position) -> Result<char, ParsingError> {
+        let escaped = match self.bump() {
+            Some('n') => '\n',
+            Some('t') => '\t',
+            Some('r') => '\r',
+            Some('\\') => '\\',
+            Some('"') => '"',
+            Some('0') => '\0',
+            Some('u') => return self.unicode_escape(start),
+            Some(ch) => {
+                return Err(ParsingError::new(
+                    ParsingErrorVariant::InvalidEscape(ch),
+                    self.context_from(start),
+                ))
+            }
+            None => {
+                return Err(ParsingError::new(
+                    ParsingErrorVariant::UnterminatedString,
+                    self.compute_context(),
+                ))
+            }
         };
 
-        let start = self.current + 1;
-        self.current += str_length + 1;
+        Ok(escaped)
+    }
 
-        let brise_string: String = self.input.chars().skip(start).take(str_length).collect();
+    /// `\u{XXXX}` with 1 to 6 hexadecimal digits, `\u` has already been consumed
+    fn unicode_escape(&mut self, start: Position) -> Result<char, ParsingError> {
+        if !self.bump_if('{') {
+            return Err(ParsingError::new(
+                ParsingErrorVariant::MalformedUnicodeEscape,
+                self.context_from(start),
+            ));
+        }
 
-        let last_new_line = brise_string.chars().rev().position(|ch| ch == '\n');
+        let digits_start = self.cursor.offset();
+        self.bump_while(|ch| ch.is_ascii_hexdigit());
+        let digits = self.cursor.slice(digits_start, self.cursor.offset());
 
-        if let Some(last_new_line) = last_new_line {
-            self.col = NonZeroUsize::new(1 + last_new_line).unwrap().into();
-            let new_lines = brise_string.chars().filter(|ch| *ch == '\n').count();
-            self.line += new_lines;
-        } else {
-            self.col += str_length + 1;
+        if !self.bump_if('}') || digits.is_empty() || digits.len() > 6 {
+            return Err(ParsingError::new(
+                ParsingErrorVariant::MalformedUnicodeEscape,
+                self.context_from(start),
+            ));
         }
 
-        Ok(TokenVariant::String(brise_string.into()))
+        // We can unwrap here, because there are at most 6 hexadecimal digits
+        let code_point = u32::from_str_radix(digits, 16).unwrap();
+
+        char::from_u32(code_point).ok_or_else(|| {
+            ParsingError::new(
+                ParsingErrorVariant::InvalidUnicodeCodePoint(code_point),
+                self.context_from(start),
+            )
+        })
     }
 
-    fn identifier(&mut self) -> TokenVariant {
-        let identifier_start = self.current;
+    /// The opening `$"` has already been consumed
+    fn formatted_string(&mut self) -> Result<TokenVariant, ParsingError> {
+        let mut parts = vec![];
+        let mut text = String::new();
+        let mut first_error = None;
 
-        while self
-            .input
-            .chars()
-            .nth(self.current + 1)
-            .is_some_and(|ch| matches!(ch, '_' | '0'..='9' | 'a'..='z' | 'A'..='Z'))
-        {
-            self.current += 1;
+        loop {
+            let char_start = self.current_position();
+            let Some(ch) = self.bump() else {
+                return Err(ParsingError::new(
+                    ParsingErrorVariant::UnterminatedString,
+                    self.compute_context(),
+                ));
+            };
+
+            match ch {
+                '"' => break,
+                '\\' => match self.escape(char_start) {
+                    Ok(escaped) => text.push(escaped),
+                    Err(error) => {
+                        first_error.get_or_insert(error);
+                    }
+                },
+                '{' if self.bump_if('{') => text.push('{'),
+                '}' if self.bump_if('}') => text.push('}'),
+                '{' => {
+                    let tokens = self.interpolation(char_start, &mut first_error)?;
+                    parts.push((std::mem::take(&mut text).into(), tokens));
+                }
+                '}' => {
+                    first_error.get_or_insert(ParsingError::new(
+                        ParsingErrorVariant::UnescapedClosingBrace,
+                        self.context_from(char_start),
+                    ));
+                }
+                _ => text.push(ch),
+            }
         }
 
-        let identifier_len = 1 + self.current - identifier_start;
-        let identifier: String = self
-            .input
-            .chars()
-            .skip(identifier_start)
-            .take(identifier_len)
-            .collect();
+        if !text.is_empty() || parts.is_empty() {
+            parts.push((text.into(), vec![]));
+        }
+
+        match first_error {
+            Some(error) => Err(error),
+            None => Ok(TokenVariant::FormattedString(parts)),
+        }
+    }
+
+    /// Lexes the tokens of a `{...}` segment of a formatted string,
+    /// the opening `{` has already been consumed
+    fn interpolation(
+        &mut self,
+        open_brace: Position,
+        first_error: &mut Option<ParsingError>,
+    ) -> Result<Vec<Token>, ParsingError> {
+        let string_start = self.token_start;
+        let mut tokens = vec![];
+        let mut depth = 0usize;
+
+        loop {
+            self.leading_trivia();
+
+            match self.cursor.peek() {
+                None => {
+                    self.token_start = string_start;
+                    return Err(ParsingError::new(
+                        ParsingErrorVariant::UnterminatedInterpolation,
+                        self.context_from(open_brace),
+                    ));
+                }
+                Some('}') if depth == 0 => {
+                    self.bump();
+                    break;
+                }
+                Some(_) => {}
+            }
+
+            // We can unwrap here, because the input is not empty
+            let token = self.lex_token().unwrap();
+            match token.variant() {
+                TokenVariant::LeftBrace => depth += 1,
+                TokenVariant::RightBrace => depth -= 1,
+                _ => {}
+            }
+            tokens.push(token);
+        }
 
-        self.col += identifier_len - 1;
+        if tokens.is_empty() {
+            first_error.get_or_insert(ParsingError::new(
+                ParsingErrorVariant::EmptyInterpolation,
+                self.context_from(open_brace),
+            ));
+        }
+
+        self.token_start = string_start;
+        Ok(tokens)
+    }
 
-        match identifier.as_str() {
+    /// Identifiers follow `XID_Start XID_Continue*` and are stored NFC normalized,
+    /// so that visually identical names are equal. The first char has already been consumed
+    fn identifier(&mut self) -> TokenVariant {
+        let start = self.token_start.offset;
+        self.bump_while(|ch| ch.is_xid_continue());
+
+        let text = self.cursor.slice(start, self.cursor.offset());
+        let identifier: Cow<str> = if is_nfc(text) {
+            text.into()
+        } else {
+            text.nfc().collect::<String>().into()
+        };
+
+        match identifier.as_ref() {
             "if" => TokenVariant::If,
             "else" => TokenVariant::Else,
             "loop" => TokenVariant::Loop,
@@ -324,7 +1016,118 @@ impl TokenParser {
             "break" => TokenVariant::Break,
             "continue" => TokenVariant::Continue,
             "return" => TokenVariant::Return,
-            _ => TokenVariant::Identifier(identifier.into()),
+            identifier => {
+                let identifier = RawString::from(identifier);
+                self.lint_identifier(identifier);
+                TokenVariant::Identifier(identifier)
+            }
+        }
+    }
+
+    /// Warns about identifiers mixing scripts and about identifiers which look like
+    /// another identifier of the input. Two ASCII identifiers are never reported as
+    /// confusable, `rn` and `m` are both legitimate names.
+    fn lint_identifier(&mut self, identifier: RawString) {
+        let text = identifier.as_str();
+
+        if !text.is_ascii() && !text.is_single_script() {
+            self.warnings.push(ParsingWarning::new(
+                ParsingWarningVariant::MixedScriptIdentifier(identifier),
+                self.compute_context(),
+            ));
+        }
+
+        let skeleton: String = skeleton(text).collect();
+        match self.skeletons.get(&skeleton) {
+            Some(similar) if *similar == identifier => {}
+            Some(similar) if similar.as_str().is_ascii() && text.is_ascii() => {}
+            Some(similar) => {
+                let variant = ParsingWarningVariant::ConfusableIdentifier {
+                    identifier,
+                    similar: *similar,
+                };
+                self.warnings
+                    .push(ParsingWarning::new(variant, self.compute_context()));
+            }
+            None => {
+                self.skeletons.insert(skeleton, identifier);
+            }
+        }
+    }
+}
+
+/// Line of a `\"\"\"` string, along with the length of its indentation
+#[derive(Debug, Default)]
+struct StringLine {
+    text: String,
+    indentation: usize,
+    has_content: bool,
+}
+
+impl StringLine {
+    /// Escaped chars are never indentation
+    fn push(&mut self, ch: char, literal: bool) {
+        let is_space = literal && matches!(ch, ' ' | '\t' | '\r');
+
+        if is_space && self.indentation == self.text.len() {
+            self.indentation += ch.len_utf8();
+        }
+        self.has_content |= !is_space;
+        self.text.push(ch);
+    }
+
+    fn indentation(&self) -> &str {
+        &self.text[..self.indentation]
+    }
+
+    fn join(mut lines: Vec<StringLine>) -> String {
+        if lines.len() == 1 {
+            return lines.remove(0).text;
+        }
+
+        if lines[0].is_blank() {
+            lines.remove(0);
         }
+        if lines.len() > 1 && lines.last().is_some_and(StringLine::is_blank) {
+            lines.pop();
+        }
+
+        let common = lines
+            .iter()
+            .filter(|line| !line.is_blank())
+            .map(StringLine::indentation)
+            .reduce(|common, indentation| {
+                let len = common
+                    .bytes()
+                    .zip(indentation.bytes())
+                    .take_while(|(a, b)| a == b)
+                    .count();
+                &common[..len]
+            })
+            .map_or(0, str::len);
+
+        lines
+            .iter()
+            .map(|line| {
+                if line.is_blank() {
+                    ""
+                } else {
+                    &line.text[common..]
+                }
+            })
+            .collect::<Vec<&str>>()
+            .join("\n")
+    }
+
+    fn is_blank(&self) -> bool {
+        !self.has_content
+    }
+}
+
+impl Iterator for TokenParser<'_> {
+    type Item = Token;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.lex_token()
     }
 }
@@ -0,0 +1,53 @@
+use std::str::Chars;
+
+/// Walks the source once, char by char, while keeping track of the byte offset.
+#[derive(Debug, Clone)]
+pub struct Cursor<'a> {
+    input: &'a str,
+    chars: Chars<'a>,
+}
+
+impl<'a> Cursor<'a> {
+    pub fn new(input: &'a str) -> Self {
+        Self {
+            input,
+            chars: input.chars(),
+        }
+    }
+
+    /// Cursor starting at a byte offset of the input
+    pub fn at(input: &'a str, offset: usize) -> Self {
+        Self {
+            input,
+            chars: input[offset..].chars(),
+        }
+    }
+
+    /// Byte offset of the next char in the input
+    pub fn offset(&self) -> usize {
+        self.input.len() - self.chars.as_str().len()
+    }
+
+    /// Input that has not been consumed yet
+    pub fn rest(&self) -> &'a str {
+        self.chars.as_str()
+    }
+
+    pub fn peek(&self) -> Option<char> {
+        self.chars.clone().next()
+    }
+
+    pub fn peek_second(&self) -> Option<char> {
+        let mut chars = self.chars.clone();
+        chars.next();
+        chars.next()
+    }
+
+    pub fn bump(&mut self) -> Option<char> {
+        self.chars.next()
+    }
+
+    pub fn slice(&self, start: usize, end: usize) -> &'a str {
+        &self.input[start..end]
+    }
+}
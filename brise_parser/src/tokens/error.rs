@@ -1,14 +1,40 @@
 use std::fmt::Display;
 
-use brise_token::BriseContext;
+use brise_token::{BriseContext, SourceMap};
 use thiserror::Error;
 
-#[derive(Debug, Error)]
+#[derive(Debug, PartialEq, Error)]
 pub enum ParsingErrorVariant {
     #[error("Unexpected character: {0}")]
     UnexpectedCharacter(char),
     #[error("Missing end of string `\"`, string started here but never end")]
     UnterminatedString,
+    #[error("Expected `\"` after the `#` starting a raw string")]
+    MissingRawStringQuote,
+    #[error("Unknown escape sequence `\\{0}`")]
+    InvalidEscape(char),
+    #[error("Malformed unicode escape, expected `\\u{{XXXX}}` with 1 to 6 hexadecimal digits")]
+    MalformedUnicodeEscape,
+    #[error("`{0:X}` is not a valid unicode code point")]
+    InvalidUnicodeCodePoint(u32),
+    #[error("A number must contain at least one digit after its prefix")]
+    MissingDigits,
+    #[error("Invalid digit `{digit}` in a base {radix} number")]
+    InvalidDigit { digit: char, radix: u32 },
+    #[error("The exponent of a number must contain at least one digit")]
+    MissingExponentDigits,
+    #[error("Integer too large to fit in 64 bits")]
+    IntegerOverflow,
+    #[error("Float too large to be represented in 64 bits")]
+    FloatOverflow,
+    #[error("Missing `}}` to close the interpolation started here")]
+    UnterminatedInterpolation,
+    #[error("An interpolation must contain an expression")]
+    EmptyInterpolation,
+    #[error("Missing `*/` to close the block comment started here")]
+    UnterminatedBlockComment,
+    #[error("Unescaped `}}` in a formatted string, use `}}}}` to insert a `}}`")]
+    UnescapedClosingBrace,
 }
 
 #[derive(Debug, Error)]
@@ -22,11 +48,42 @@ impl ParsingError {
     pub fn new(variant: ParsingErrorVariant, context: BriseContext) -> Self {
         Self { variant, context }
     }
+
+    pub fn variant(&self) -> &ParsingErrorVariant {
+        &self.variant
+    }
+
+    pub fn context(&self) -> &BriseContext {
+        &self.context
+    }
+
+    pub fn into_parts(self) -> (ParsingErrorVariant, BriseContext) {
+        (self.variant, self.context)
+    }
+
+    /// Error message along with the source line where the error happened
+    pub fn render(&self, source_map: &SourceMap) -> String {
+        source_map.render(&self.context, &self.variant)
+    }
 }
 
 #[derive(Debug, Error)]
 pub struct ParsingErrors(Vec<ParsingError>);
 
+impl ParsingErrors {
+    pub fn errors(&self) -> &[ParsingError] {
+        &self.0
+    }
+
+    pub fn render(&self, source_map: &SourceMap) -> String {
+        self.0
+            .iter()
+            .map(|e| e.render(source_map))
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+}
+
 impl From<ParsingError> for ParsingErrors {
     fn from(value: ParsingError) -> Self {
         Self(vec![value])
@@ -0,0 +1,135 @@
+use brise_token::{FileId, Token, TokenVariant};
+
+use super::TokenParser;
+
+/// Number of tokens before a token that may merge with it when they are not
+/// separated: `1`, `.` and `5` are lexed as `1.5`
+const WINDOW: usize = 2;
+
+/// Renders tokens back into source code which lexes to the same tokens,
+/// with a space only where two tokens would otherwise merge
+#[derive(Debug, Default)]
+pub struct TokenPrinter<'a> {
+    output: String,
+    /// Start in the output of the last tokens printed, along with the tokens
+    window: Vec<(usize, &'a Token)>,
+}
+
+impl<'a> TokenPrinter<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// `Error` and `EndOfFile` tokens have no source text, they are skipped
+    pub fn print(tokens: &'a [Token]) -> String {
+        let mut printer = Self::new();
+
+        for token in tokens {
+            printer.push(token);
+        }
+
+        printer.finish()
+    }
+
+    pub fn push(&mut self, token: &'a Token) {
+        let text = Self::token_text(token.variant());
+        if text.is_empty() {
+            return;
+        }
+
+        let window_start = self
+            .window
+            .first()
+            .map_or(self.output.len(), |(start, _)| *start);
+        let joined = format!("{}{text}", &self.output[window_start..]);
+        if !self.lexes_back(&joined, token) {
+            self.output.push(' ');
+        }
+
+        if self.window.len() == WINDOW {
+            self.window.remove(0);
+        }
+        self.window.push((self.output.len(), token));
+        self.output.push_str(&text);
+    }
+
+    pub fn finish(self) -> String {
+        self.output
+    }
+
+    /// Whether the text lexes to the tokens of the window followed by the token
+    fn lexes_back(&self, text: &str, token: &Token) -> bool {
+        let expected = self
+            .window
+            .iter()
+            .map(|(_, token)| *token)
+            .chain(Some(token));
+        let mut parser = TokenParser::new(FileId::default(), text);
+        let lexed: Vec<Token> = parser.by_ref().collect();
+
+        parser.errors().is_empty()
+            && lexed.len() == self.window.len() + 1
+            && lexed
+                .iter()
+                .zip(expected)
+                .all(|(lexed, expected)| lexed.same_variant(expected))
+    }
+
+    fn token_text(variant: &TokenVariant) -> String {
+        match variant {
+            TokenVariant::Identifier(identifier) => identifier.to_string(),
+            TokenVariant::String(text) => format!("\"{}\"", Self::escape(text.as_str(), false)),
+            TokenVariant::FormattedString(parts) => {
+                let mut text = String::from("$\"");
+
+                for (part, tokens) in parts {
+                    text.push_str(&Self::escape(part.as_str(), true));
+                    if !tokens.is_empty() {
+                        text.push('{');
+                        text.push_str(&TokenPrinter::print(tokens));
+                        text.push('}');
+                    }
+                }
+                text.push('"');
+
+                text
+            }
+            TokenVariant::Integer(value) => value.to_string(),
+            // No literal holds an infinite value, this one is reported as too large
+            TokenVariant::Float(value) if value.is_infinite() => "1e999".into(),
+            // The debug format is the shortest text parsing back to the same value
+            TokenVariant::Float(value) => format!("{value:?}"),
+            // A doc comment runs until the end of its line. A `\r` before a `\n` would be
+            // read as part of the line ending, a trailing one is kept by ending with `\r\n`.
+            TokenVariant::DocComment(text) if text.as_str().ends_with('\r') => {
+                format!("///{text}\r\n")
+            }
+            TokenVariant::DocComment(text) => format!("///{text}\n"),
+            TokenVariant::Error | TokenVariant::EndOfFile => String::new(),
+            variant => variant.to_string(),
+        }
+    }
+
+    /// Escapes the text of a string literal, braces are doubled in formatted strings
+    fn escape(text: &str, formatted: bool) -> String {
+        let mut escaped = String::with_capacity(text.len());
+
+        for ch in text.chars() {
+            match ch {
+                '"' => escaped.push_str("\\\""),
+                '\\' => escaped.push_str("\\\\"),
+                '\n' => escaped.push_str("\\n"),
+                '\r' => escaped.push_str("\\r"),
+                '\t' => escaped.push_str("\\t"),
+                '\0' => escaped.push_str("\\0"),
+                '{' | '}' if formatted => {
+                    escaped.push(ch);
+                    escaped.push(ch);
+                }
+                ch => escaped.push(ch),
+            }
+        }
+
+        escaped
+    }
+}
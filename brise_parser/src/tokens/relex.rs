@@ -0,0 +1,180 @@
+use brise_token::{BriseContext, Line, SourceMap, Span, TextEdit};
+
+use super::{error::ParsingError, warning::ParsingWarning, LexedTokens, Position, TokenParser};
+
+/// Number of chars the lexer may read past the end of a token and of its trivia,
+/// `////` has to be read to tell a comment apart from a `///` doc comment
+const LOOKAHEAD: usize = 4;
+
+impl TokenParser<'_> {
+    /// Applies the edit to the source of the tokens and lexes again only the region
+    /// around it. Lexing stops as soon as a token starts where a previous token
+    /// started after the edit, the following tokens are moved instead of being lexed.
+    ///
+    /// Confusable identifiers are only looked for inside the region lexed again.
+    pub fn relex(
+        source_map: &mut SourceMap,
+        previous: LexedTokens,
+        edit: &TextEdit,
+    ) -> LexedTokens {
+        let LexedTokens {
+            file,
+            mut tokens,
+            errors,
+            warnings,
+            lossless,
+        } = previous;
+        let edit_span = edit.span();
+        let old_text = source_map.text(file);
+
+        // Tokens far enough from the edit are lexed the same way
+        let kept = tokens.partition_point(|token| {
+            let end = token.full_span().end();
+
+            end <= edit_span.start()
+                && old_text[end..edit_span.start()]
+                    .chars()
+                    .nth(LOOKAHEAD - 1)
+                    .is_some()
+        });
+        // The trailing trivia of the last of them run up to the end of its line, and the
+        // edit may turn what follows into trivia, like the end of a `/*` comment
+        let kept = kept.saturating_sub(1);
+        let restart = kept
+            .checked_sub(1)
+            .map_or(0, |last| tokens[last].full_span().end());
+        let reusable = tokens.partition_point(|token| token.full_span().start() < edit_span.end());
+
+        let (edit_end_line, _) = source_map.location(file, edit_span.end());
+        let line_delta = edit.replacement().matches('\n').count() as isize
+            - old_text[edit_span.start()..edit_span.end()]
+                .matches('\n')
+                .count() as isize;
+
+        source_map.edit(file, edit);
+        let text = source_map.text(file);
+
+        let mut reusable = tokens.split_off(kept.max(reusable)).into_iter().peekable();
+        tokens.truncate(kept);
+
+        let (line, col) = source_map.location(file, restart);
+        let start = Position {
+            offset: restart,
+            line,
+            col,
+        };
+        let mut parser = TokenParser::resume(file, text, start, lossless);
+        // End of the lexed region, in the text after the edit
+        let mut lexed_end = text.len();
+        let mut resynced = false;
+
+        for token in parser.by_ref() {
+            let start = token.full_span().start();
+            let old_start = start.checked_add_signed(-edit.delta());
+
+            while reusable
+                .next_if(|old| Some(old.full_span().start()) < old_start)
+                .is_some()
+            {}
+
+            // A byte order mark or a shebang is only trivia at the start of the input,
+            // a token can't be moved to or away from it
+            let same_at_start = (start == 0) == (old_start == Some(0));
+
+            if same_at_start
+                && reusable
+                    .peek()
+                    .is_some_and(|old| Some(old.full_span().start()) == old_start)
+            {
+                lexed_end = start;
+                resynced = true;
+                break;
+            }
+            tokens.push(token);
+        }
+
+        let relocate = |context: &BriseContext| {
+            let span = context.span().shifted(edit.delta());
+            let (line, col) = if context.line() == edit_end_line {
+                source_map.location(file, span.start())
+            } else {
+                let line_index = (context.line().get() - 1).saturating_add_signed(line_delta);
+                (Line::default() + line_index, context.col())
+            };
+
+            BriseContext::new(file, span, line, col)
+        };
+        // Every diagnostic is inside the token it comes from
+        let old_region = Span::new(restart, lexed_end.saturating_add_signed(-edit.delta()));
+
+        if resynced {
+            tokens.extend(reusable.map(|mut token| {
+                token.relocate(&relocate);
+                token
+            }));
+        }
+
+        let errors = merge(
+            errors,
+            parser.errors,
+            old_region,
+            lexed_end,
+            |error| error.context().span(),
+            |error| {
+                let (variant, context) = error.into_parts();
+                ParsingError::new(variant, relocate(&context))
+            },
+        );
+        let warnings = merge(
+            warnings,
+            parser.warnings,
+            old_region,
+            lexed_end,
+            |warning| warning.context().span(),
+            |warning| {
+                let (variant, context) = warning.into_parts();
+                ParsingWarning::new(variant, relocate(&context))
+            },
+        );
+
+        LexedTokens {
+            file,
+            tokens,
+            errors,
+            warnings,
+            lossless,
+        }
+    }
+}
+
+/// Replaces the old diagnostics of the region lexed again by the new ones,
+/// moving the old diagnostics after the region
+fn merge<T>(
+    old: Vec<T>,
+    new: Vec<T>,
+    old_region: Span,
+    new_end: usize,
+    span: impl Fn(&T) -> Span,
+    relocate: impl Fn(T) -> T,
+) -> Vec<T> {
+    let mut merged = vec![];
+    let mut after = vec![];
+
+    for diagnostic in old {
+        let start = span(&diagnostic).start();
+
+        if start < old_region.start() {
+            merged.push(diagnostic);
+        } else if start >= old_region.end() {
+            after.push(relocate(diagnostic));
+        }
+    }
+
+    merged.extend(
+        new.into_iter()
+            .filter(|diagnostic| span(diagnostic).start() < new_end),
+    );
+    merged.extend(after);
+
+    merged
+}
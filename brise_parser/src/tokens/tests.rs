@@ -1,28 +1,43 @@
-use std::num::NonZeroUsize;
+use std::{num::NonZeroUsize, ops::Range};
 
 use super::*;
-use brise_token::{BriseContext, Column, Line, Token, TokenVariant};
+use brise_token::{
+    BriseContext, Column, ColumnUnit, Line, LineIndex, TextEdit, Token, TokenVariant,
+};
 
-fn token(variant: TokenVariant, line: usize, col: usize) -> Token {
+fn token(variant: TokenVariant, line: usize, col: usize, span: Range<usize>) -> Token {
     Token::new(
         variant,
         BriseContext::new(
-            None,
+            FileId::default(),
+            span,
             Line::from(NonZeroUsize::new(line).unwrap()),
             Column::from(NonZeroUsize::new(col).unwrap()),
         ),
     )
 }
 
+fn lex_errors(input: &str) -> Vec<ParsingError> {
+    let (_, errors, _) = TokenParser::new(FileId::default(), input)
+        .finish()
+        .into_parts();
+
+    errors
+}
+
 #[test]
 fn parse_integer() {
     let numbers = [1, 5, 56, 891, 120003, 145560321];
-    let expected_numbers = [1.0, 5.0, 56.0, 891.0, 120003.0, 145560321.0];
 
-    for (num, result) in numbers.iter().zip(expected_numbers) {
-        let tokens = TokenParser::parse(num.to_string()).unwrap();
+    for num in numbers {
+        let input = num.to_string();
+        let input_len = input.len();
+        let tokens = TokenParser::parse(&mut SourceMap::default(), input).unwrap();
 
-        assert_eq!(vec![token(TokenVariant::Number(result), 1, 1)], tokens);
+        assert_eq!(
+            vec![token(TokenVariant::Integer(num), 1, 1, 0..input_len)],
+            tokens
+        );
     }
 }
 
@@ -31,9 +46,14 @@ fn parse_float() {
     let numbers = [2.0, 3.4, 8.41, 6.5982, 516.02, 123.981, 1482.0];
 
     for num in numbers {
-        let tokens = TokenParser::parse(num.to_string()).unwrap();
+        let input = format!("{num:?}");
+        let input_len = input.len();
+        let tokens = TokenParser::parse(&mut SourceMap::default(), input).unwrap();
 
-        assert_eq!(vec![token(TokenVariant::Number(num), 1, 1)], tokens);
+        assert_eq!(
+            vec![token(TokenVariant::Float(num), 1, 1, 0..input_len)],
+            tokens
+        );
     }
 }
 
@@ -44,12 +64,12 @@ fn two_numbers() {
 
     let input = format!("{number1:?} {number2:?}");
 
-    let tokens = TokenParser::parse(input).unwrap();
+    let tokens = TokenParser::parse(&mut SourceMap::default(), input).unwrap();
 
     assert_eq!(
         vec![
-            token(TokenVariant::Number(number1), 1, 1),
-            token(TokenVariant::Number(number2), 1, 6)
+            token(TokenVariant::Float(number1), 1, 1, 0..4),
+            token(TokenVariant::Float(number2), 1, 6, 5..9)
         ],
         tokens
     )
@@ -60,10 +80,15 @@ fn parse_string() {
     let brise_string = "my string";
     let string = format!("\"{brise_string}\"");
 
-    let tokens = TokenParser::parse(string.clone()).unwrap();
+    let tokens = TokenParser::parse(&mut SourceMap::default(), string.clone()).unwrap();
 
     assert_eq!(
-        vec![token(TokenVariant::String(brise_string.into()), 1, 1)],
+        vec![token(
+            TokenVariant::String(brise_string.into()),
+            1,
+            1,
+            0..11
+        )],
         tokens
     );
 }
@@ -72,14 +97,14 @@ fn parse_string() {
 fn parse_string_with_multiple_lines() {
     let brise_string = r"my
 string";
-    let number = 54.0;
+    let number = 54;
     let string = format!("\"{brise_string}\"{number}");
 
-    let tokens = TokenParser::parse(string.clone()).unwrap();
+    let tokens = TokenParser::parse(&mut SourceMap::default(), string.clone()).unwrap();
 
     let expected_tokens = vec![
-        token(TokenVariant::String(brise_string.into()), 1, 1),
-        token(TokenVariant::Number(number), 2, 8),
+        token(TokenVariant::String(brise_string.into()), 1, 1, 0..11),
+        token(TokenVariant::Integer(number), 2, 8, 11..13),
     ];
 
     assert_eq!(expected_tokens, tokens);
@@ -89,11 +114,11 @@ string";
 fn parse_identifier() {
     let input = "let a";
 
-    let tokens = TokenParser::parse(input.into()).unwrap();
+    let tokens = TokenParser::parse(&mut SourceMap::default(), input.into()).unwrap();
 
     let expected_tokens = vec![
-        token(TokenVariant::Let, 1, 1),
-        token(TokenVariant::Identifier("a".into()), 1, 5),
+        token(TokenVariant::Let, 1, 1, 0..3),
+        token(TokenVariant::Identifier("a".into()), 1, 5, 4..5),
     ];
 
     assert_eq!(expected_tokens, tokens);
@@ -103,14 +128,14 @@ fn parse_identifier() {
 fn parse_variable_definition() {
     let input = "let sasuke = \"sasuke\";";
 
-    let tokens = TokenParser::parse(input.into()).unwrap();
+    let tokens = TokenParser::parse(&mut SourceMap::default(), input.into()).unwrap();
 
     let expected_tokens = vec![
-        token(TokenVariant::Let, 1, 1),
-        token(TokenVariant::Identifier("sasuke".into()), 1, 5),
-        token(TokenVariant::Equal, 1, 12),
-        token(TokenVariant::String("sasuke".into()), 1, 14),
-        token(TokenVariant::Semicolon, 1, 22),
+        token(TokenVariant::Let, 1, 1, 0..3),
+        token(TokenVariant::Identifier("sasuke".into()), 1, 5, 4..10),
+        token(TokenVariant::Equal, 1, 12, 11..12),
+        token(TokenVariant::String("sasuke".into()), 1, 14, 13..21),
+        token(TokenVariant::Semicolon, 1, 22, 21..22),
     ];
 
     assert_eq!(expected_tokens, tokens);
@@ -120,20 +145,20 @@ fn parse_variable_definition() {
 fn parse_function_definition() {
     let input = "fn myfunction(a: number) -> number {}";
 
-    let tokens = TokenParser::parse(input.into()).unwrap();
+    let tokens = TokenParser::parse(&mut SourceMap::default(), input.into()).unwrap();
 
     let expected_tokens = vec![
-        token(TokenVariant::Fn, 1, 1),
-        token(TokenVariant::Identifier("myfunction".into()), 1, 4),
-        token(TokenVariant::LeftParen, 1, 14),
-        token(TokenVariant::Identifier("a".into()), 1, 15),
-        token(TokenVariant::Colon, 1, 16),
-        token(TokenVariant::Identifier("number".into()), 1, 18),
-        token(TokenVariant::RightParen, 1, 24),
-        token(TokenVariant::RightArrow, 1, 26),
-        token(TokenVariant::Identifier("number".into()), 1, 29),
-        token(TokenVariant::LeftBrace, 1, 36),
-        token(TokenVariant::RightBrace, 1, 37),
+        token(TokenVariant::Fn, 1, 1, 0..2),
+        token(TokenVariant::Identifier("myfunction".into()), 1, 4, 3..13),
+        token(TokenVariant::LeftParen, 1, 14, 13..14),
+        token(TokenVariant::Identifier("a".into()), 1, 15, 14..15),
+        token(TokenVariant::Colon, 1, 16, 15..16),
+        token(TokenVariant::Identifier("number".into()), 1, 18, 17..23),
+        token(TokenVariant::RightParen, 1, 24, 23..24),
+        token(TokenVariant::RightArrow, 1, 26, 25..27),
+        token(TokenVariant::Identifier("number".into()), 1, 29, 28..34),
+        token(TokenVariant::LeftBrace, 1, 36, 35..36),
+        token(TokenVariant::RightBrace, 1, 37, 36..37),
     ];
 
     assert_eq!(expected_tokens, tokens);
@@ -143,17 +168,1213 @@ fn parse_function_definition() {
 fn parse_function_call() {
     let input = "myfunction(a, b);";
 
-    let tokens = TokenParser::parse(input.into()).unwrap();
+    let tokens = TokenParser::parse(&mut SourceMap::default(), input.into()).unwrap();
+
+    let expected_tokens = vec![
+        token(TokenVariant::Identifier("myfunction".into()), 1, 1, 0..10),
+        token(TokenVariant::LeftParen, 1, 11, 10..11),
+        token(TokenVariant::Identifier("a".into()), 1, 12, 11..12),
+        token(TokenVariant::Comma, 1, 13, 12..13),
+        token(TokenVariant::Identifier("b".into()), 1, 15, 14..15),
+        token(TokenVariant::RightParen, 1, 16, 15..16),
+        token(TokenVariant::Semicolon, 1, 17, 16..17),
+    ];
+
+    assert_eq!(expected_tokens, tokens);
+}
+
+#[test]
+fn parse_newline_resets_column() {
+    let input = "let a\n  b";
+
+    let tokens = TokenParser::parse(&mut SourceMap::default(), input.into()).unwrap();
+
+    let expected_tokens = vec![
+        token(TokenVariant::Let, 1, 1, 0..3),
+        token(TokenVariant::Identifier("a".into()), 1, 5, 4..5),
+        token(TokenVariant::Identifier("b".into()), 2, 3, 8..9),
+    ];
+
+    assert_eq!(expected_tokens, tokens);
+}
+
+#[test]
+fn parse_reports_every_error() {
+    let input = "a # b @";
+
+    let errors = lex_errors(input);
+
+    assert_eq!(2, errors.len());
+}
+
+#[test]
+fn lexing_is_linear() {
+    fn lex_duration(lines: usize) -> std::time::Duration {
+        let input = "let value = 12.5 + \"some text\" * (left - right);\n".repeat(lines);
+
+        (0..3)
+            .map(|_| {
+                let start = std::time::Instant::now();
+                let tokens =
+                    TokenParser::parse_deque(&mut SourceMap::default(), input.clone()).unwrap();
+                let duration = start.elapsed();
+                assert_eq!(13 * lines, tokens.len());
+                duration
+            })
+            .min()
+            .unwrap()
+    }
+
+    let small = lex_duration(2_000);
+    let large = lex_duration(16_000);
+
+    // A quadratic lexer would be around 64 times slower on the large input
+    assert!(
+        large < small * 24,
+        "lexing 8x more input took {large:?} instead of {small:?}"
+    );
+}
+
+#[test]
+fn unterminated_string_spans_to_the_end() {
+    let input = "a \"never\nclosed";
+
+    let errors: Vec<ParsingError> = lex_errors(input);
+
+    assert_eq!(1, errors.len());
+    assert_eq!(Span::new(2, input.len()), errors[0].context().span());
+}
+
+#[test]
+fn parse_formatted_string() {
+    let input = "$\"a {b + 1} c\"";
+
+    let tokens = TokenParser::parse(&mut SourceMap::default(), input.into()).unwrap();
+
+    let expected_tokens = vec![token(
+        TokenVariant::FormattedString(vec![
+            (
+                "a ".into(),
+                vec![
+                    token(TokenVariant::Identifier("b".into()), 1, 6, 5..6),
+                    token(TokenVariant::Plus, 1, 8, 7..8),
+                    token(TokenVariant::Integer(1), 1, 10, 9..10),
+                ],
+            ),
+            (" c".into(), vec![]),
+        ]),
+        1,
+        1,
+        0..14,
+    )];
+
+    assert_eq!(expected_tokens, tokens);
+}
+
+#[test]
+fn parse_formatted_string_with_escaped_braces() {
+    let input = "$\"{{x}} {\n{y}}\" 2";
+
+    let tokens = TokenParser::parse(&mut SourceMap::default(), input.into()).unwrap();
+
+    let expected_tokens = vec![
+        token(
+            TokenVariant::FormattedString(vec![(
+                "{x} ".into(),
+                vec![
+                    token(TokenVariant::LeftBrace, 2, 1, 10..11),
+                    token(TokenVariant::Identifier("y".into()), 2, 2, 11..12),
+                    token(TokenVariant::RightBrace, 2, 3, 12..13),
+                ],
+            )]),
+            1,
+            1,
+            0..15,
+        ),
+        token(TokenVariant::Integer(2), 2, 7, 16..17),
+    ];
+
+    assert_eq!(expected_tokens, tokens);
+}
+
+#[test]
+fn parse_formatted_string_with_nested_string() {
+    let input = "$\"{ \"}\" }\"";
+
+    let tokens = TokenParser::parse(&mut SourceMap::default(), input.into()).unwrap();
+
+    let expected_tokens = vec![token(
+        TokenVariant::FormattedString(vec![(
+            "".into(),
+            vec![token(TokenVariant::String("}".into()), 1, 5, 4..7)],
+        )]),
+        1,
+        1,
+        0..10,
+    )];
+
+    assert_eq!(expected_tokens, tokens);
+}
+
+#[test]
+fn unterminated_interpolation() {
+    let input = "$\"a {b";
+
+    let errors: Vec<ParsingError> = lex_errors(input);
+
+    assert_eq!(1, errors.len());
+    assert!(matches!(
+        errors[0].variant(),
+        ParsingErrorVariant::UnterminatedInterpolation
+    ));
+    assert_eq!(Span::new(4, 6), errors[0].context().span());
+}
+
+#[test]
+fn formatted_string_errors() {
+    let inputs = [
+        ("$\"a {} b\"", 4..6),
+        ("$\"a } b\"", 4..5),
+        ("$\"a {b", 4..6),
+        ("$\"a {b}", 0..7),
+    ];
+
+    for (input, span) in inputs {
+        let errors: Vec<ParsingError> = lex_errors(input);
+
+        assert_eq!(1, errors.len(), "{input}");
+        assert_eq!(Span::from(span), errors[0].context().span(), "{input}");
+    }
+}
+
+#[test]
+fn parse_comments() {
+    let input = "a // comment\n/* block /* nested */ still */ b /**/ / c //// not doc";
+
+    let tokens = TokenParser::parse(&mut SourceMap::default(), input.into()).unwrap();
+
+    let expected_tokens = vec![
+        token(TokenVariant::Identifier("a".into()), 1, 1, 0..1),
+        token(TokenVariant::Identifier("b".into()), 2, 32, 44..45),
+        token(TokenVariant::Slash, 2, 39, 51..52),
+        token(TokenVariant::Identifier("c".into()), 2, 41, 53..54),
+    ];
+
+    assert_eq!(expected_tokens, tokens);
+}
+
+#[test]
+fn parse_doc_comment() {
+    let input = "/// Adds two numbers\r\nfn add";
+
+    let tokens = TokenParser::parse(&mut SourceMap::default(), input.into()).unwrap();
+
+    let expected_tokens = vec![
+        token(
+            TokenVariant::DocComment(" Adds two numbers".into()),
+            1,
+            1,
+            0..20,
+        ),
+        token(TokenVariant::Fn, 2, 1, 22..24),
+        token(TokenVariant::Identifier("add".into()), 2, 4, 25..28),
+    ];
+
+    assert_eq!(expected_tokens, tokens);
+}
+
+#[test]
+fn unterminated_block_comment() {
+    let input = "a /* open /* nested */ \n b";
+
+    let (tokens, errors, _) = TokenParser::new(FileId::default(), input)
+        .finish()
+        .into_parts();
+
+    assert_eq!(
+        vec![
+            token(TokenVariant::Identifier("a".into()), 1, 1, 0..1),
+            token(TokenVariant::Error, 1, 3, 2..input.len()),
+        ],
+        tokens
+    );
+    assert_eq!(1, errors.len());
+    let error = &errors[0];
+    assert!(matches!(
+        error.variant(),
+        ParsingErrorVariant::UnterminatedBlockComment
+    ));
+    assert_eq!(Span::new(2, input.len()), error.context().span());
+    assert_eq!(
+        Column::from(NonZeroUsize::new(3).unwrap()),
+        error.context().col()
+    );
+}
+
+#[test]
+fn parse_string_escapes() {
+    let input = r#""a\n\t\r\\\"\0\u{48}\u{1F600}" $"\"{x}\u{e9}""#;
+
+    let tokens = TokenParser::parse(&mut SourceMap::default(), input.into()).unwrap();
+
+    let expected_tokens = vec![
+        token(
+            TokenVariant::String("a\n\t\r\\\"\0H\u{1F600}".into()),
+            1,
+            1,
+            0..30,
+        ),
+        token(
+            TokenVariant::FormattedString(vec![
+                (
+                    "\"".into(),
+                    vec![token(TokenVariant::Identifier("x".into()), 1, 37, 36..37)],
+                ),
+                ("é".into(), vec![]),
+            ]),
+            1,
+            32,
+            31..45,
+        ),
+    ];
+
+    assert_eq!(expected_tokens, tokens);
+}
+
+#[test]
+fn invalid_escapes() {
+    let inputs = [
+        (r#"  "ab\q""#, 5..7, ParsingErrorVariant::InvalidEscape('q')),
+        (
+            r#""\u{}""#,
+            1..5,
+            ParsingErrorVariant::MalformedUnicodeEscape,
+        ),
+        (
+            r#""\u41""#,
+            1..3,
+            ParsingErrorVariant::MalformedUnicodeEscape,
+        ),
+        (
+            r#""\u{1234567}""#,
+            1..12,
+            ParsingErrorVariant::MalformedUnicodeEscape,
+        ),
+        (
+            r#""\u{D800}""#,
+            1..9,
+            ParsingErrorVariant::InvalidUnicodeCodePoint(0xD800),
+        ),
+        (
+            r#""\u{110000}""#,
+            1..11,
+            ParsingErrorVariant::InvalidUnicodeCodePoint(0x110000),
+        ),
+        (r#"$"{a}\z""#, 5..7, ParsingErrorVariant::InvalidEscape('z')),
+    ];
+
+    for (input, span, variant) in inputs {
+        let errors: Vec<ParsingError> = lex_errors(input);
+
+        assert_eq!(1, errors.len(), "{input}");
+        assert_eq!(&variant, errors[0].variant(), "{input}");
+        assert_eq!(
+            Span::from(span.clone()),
+            errors[0].context().span(),
+            "{input}"
+        );
+        assert_eq!(
+            Column::from(NonZeroUsize::new(span.start + 1).unwrap()),
+            errors[0].context().col(),
+            "{input}"
+        );
+    }
+}
+
+#[test]
+fn escaped_quote_does_not_end_string() {
+    let input = r#""\""#;
+
+    let errors: Vec<ParsingError> = lex_errors(input);
+
+    assert_eq!(1, errors.len());
+    assert!(matches!(
+        errors[0].variant(),
+        ParsingErrorVariant::UnterminatedString
+    ));
+}
+
+#[test]
+fn parse_number_syntax() {
+    let inputs = [
+        ("0xFF", TokenVariant::Integer(255)),
+        ("0x_dead_BEEF", TokenVariant::Integer(0xdead_beef)),
+        ("0o17", TokenVariant::Integer(15)),
+        ("0b1010_1010", TokenVariant::Integer(170)),
+        ("1_000_000", TokenVariant::Integer(1_000_000)),
+        ("9223372036854775807", TokenVariant::Integer(i64::MAX)),
+        ("1e-9", TokenVariant::Float(1e-9)),
+        ("2.5E+3", TokenVariant::Float(2.5e3)),
+        ("1_0.2_5e1_0", TokenVariant::Float(10.25e10)),
+    ];
+
+    for (input, variant) in inputs {
+        let tokens = TokenParser::parse(&mut SourceMap::default(), input.into()).unwrap();
+
+        assert_eq!(vec![token(variant, 1, 1, 0..input.len())], tokens);
+    }
+}
+
+#[test]
+fn dot_after_integer() {
+    let input = "1.foo 1..2 3.";
+
+    let tokens = TokenParser::parse(&mut SourceMap::default(), input.into()).unwrap();
+
+    let expected_tokens = vec![
+        token(TokenVariant::Integer(1), 1, 1, 0..1),
+        token(TokenVariant::Dot, 1, 2, 1..2),
+        token(TokenVariant::Identifier("foo".into()), 1, 3, 2..5),
+        token(TokenVariant::Integer(1), 1, 7, 6..7),
+        token(TokenVariant::DotDot, 1, 8, 7..9),
+        token(TokenVariant::Integer(2), 1, 10, 9..10),
+        token(TokenVariant::Integer(3), 1, 12, 11..12),
+        token(TokenVariant::Dot, 1, 13, 12..13),
+    ];
+
+    assert_eq!(expected_tokens, tokens);
+}
+
+#[test]
+fn malformed_numbers() {
+    let inputs = [
+        ("0x", ParsingErrorVariant::MissingDigits),
+        ("0b_", ParsingErrorVariant::MissingDigits),
+        (
+            "0b102",
+            ParsingErrorVariant::InvalidDigit {
+                digit: '2',
+                radix: 2,
+            },
+        ),
+        (
+            "0o8",
+            ParsingErrorVariant::InvalidDigit {
+                digit: '8',
+                radix: 8,
+            },
+        ),
+        (
+            "0xFG",
+            ParsingErrorVariant::InvalidDigit {
+                digit: 'G',
+                radix: 16,
+            },
+        ),
+        (
+            "12abc",
+            ParsingErrorVariant::InvalidDigit {
+                digit: 'a',
+                radix: 10,
+            },
+        ),
+        ("1e", ParsingErrorVariant::MissingExponentDigits),
+        ("1.5e-", ParsingErrorVariant::MissingExponentDigits),
+        ("9223372036854775808", ParsingErrorVariant::IntegerOverflow),
+        (
+            "0x1_0000_0000_0000_0000",
+            ParsingErrorVariant::IntegerOverflow,
+        ),
+        ("1e999", ParsingErrorVariant::FloatOverflow),
+        ("1_000.5e400", ParsingErrorVariant::FloatOverflow),
+    ];
+
+    for (input, variant) in inputs {
+        let (tokens, errors, _) = TokenParser::new(FileId::default(), input)
+            .finish()
+            .into_parts();
+
+        assert_eq!(
+            vec![token(TokenVariant::Error, 1, 1, 0..input.len())],
+            tokens,
+            "{input}"
+        );
+        assert_eq!(1, errors.len(), "{input}");
+        let error = &errors[0];
+        assert_eq!(&variant, error.variant(), "{input}");
+        assert_eq!(Span::new(0, input.len()), error.context().span(), "{input}");
+    }
+}
+
+#[test]
+fn parse_operators() {
+    let input = "== => = % %= ** *= * += + -= -> - /= / .. ..= . :: : |> || && != !> !";
+
+    let variants: Vec<TokenVariant> = TokenParser::parse(&mut SourceMap::default(), input.into())
+        .unwrap()
+        .into_iter()
+        .map(TokenVariant::from)
+        .collect();
+
+    let expected_variants = vec![
+        TokenVariant::EqualEqual,
+        TokenVariant::FatArrow,
+        TokenVariant::Equal,
+        TokenVariant::Percent,
+        TokenVariant::PercentEqual,
+        TokenVariant::StarStar,
+        TokenVariant::StarEqual,
+        TokenVariant::Star,
+        TokenVariant::PlusEqual,
+        TokenVariant::Plus,
+        TokenVariant::MinusEqual,
+        TokenVariant::RightArrow,
+        TokenVariant::Minus,
+        TokenVariant::SlashEqual,
+        TokenVariant::Slash,
+        TokenVariant::DotDot,
+        TokenVariant::DotDotEqual,
+        TokenVariant::Dot,
+        TokenVariant::ColonColon,
+        TokenVariant::Colon,
+        TokenVariant::BarGreater,
+        TokenVariant::BarBar,
+        TokenVariant::AmpersandAmpersand,
+        TokenVariant::BangEqual,
+        TokenVariant::BangRightChevron,
+        TokenVariant::Bang,
+    ];
+
+    assert_eq!(expected_variants, variants);
+}
+
+#[test]
+fn parse_range_between_integers() {
+    let input = "0..=10";
+
+    let tokens = TokenParser::parse(&mut SourceMap::default(), input.into()).unwrap();
+
+    let expected_tokens = vec![
+        token(TokenVariant::Integer(0), 1, 1, 0..1),
+        token(TokenVariant::DotDotEqual, 1, 2, 1..4),
+        token(TokenVariant::Integer(10), 1, 5, 4..6),
+    ];
+
+    assert_eq!(expected_tokens, tokens);
+}
+
+#[test]
+fn parse_unicode_identifiers() {
+    let input = "let café = переменная + 変数_2;";
+
+    let tokens = TokenParser::parse(&mut SourceMap::default(), input.into()).unwrap();
+
+    let expected_tokens = vec![
+        token(TokenVariant::Let, 1, 1, 0..3),
+        token(TokenVariant::Identifier("café".into()), 1, 5, 4..9),
+        token(TokenVariant::Equal, 1, 10, 10..11),
+        token(TokenVariant::Identifier("переменная".into()), 1, 12, 12..32),
+        token(TokenVariant::Plus, 1, 23, 33..34),
+        token(TokenVariant::Identifier("変数_2".into()), 1, 25, 35..43),
+        token(TokenVariant::Semicolon, 1, 29, 43..44),
+    ];
+
+    assert_eq!(expected_tokens, tokens);
+}
+
+#[test]
+fn identifiers_are_nfc_normalized() {
+    let composed = "caf\u{e9}";
+    let decomposed = "cafe\u{301}";
+    let input = format!("{composed} {decomposed}");
+
+    let variants: Vec<TokenVariant> = TokenParser::parse(&mut SourceMap::default(), input)
+        .unwrap()
+        .into_iter()
+        .map(TokenVariant::from)
+        .collect();
+
+    assert_eq!(
+        vec![
+            TokenVariant::Identifier(composed.into()),
+            TokenVariant::Identifier(composed.into())
+        ],
+        variants
+    );
+}
+
+#[test]
+fn non_identifier_characters() {
+    let errors: Vec<ParsingError> = lex_errors("a 😀 ²");
+
+    let variants: Vec<&ParsingErrorVariant> = errors.iter().map(ParsingError::variant).collect();
+    assert_eq!(
+        vec![
+            &ParsingErrorVariant::UnexpectedCharacter('😀'),
+            &ParsingErrorVariant::UnexpectedCharacter('²')
+        ],
+        variants
+    );
+}
+
+#[test]
+fn mixed_script_and_confusable_identifiers() {
+    // The `а` of the second identifier is cyrillic
+    let input = "paypal pаypal rn m";
+
+    let mut parser = TokenParser::new(FileId::default(), input);
+    assert_eq!(4, parser.by_ref().count());
+
+    let warnings: Vec<&ParsingWarningVariant> = parser
+        .warnings()
+        .iter()
+        .map(ParsingWarning::variant)
+        .collect();
+    assert_eq!(
+        vec![
+            &ParsingWarningVariant::MixedScriptIdentifier("pаypal".into()),
+            &ParsingWarningVariant::ConfusableIdentifier {
+                identifier: "pаypal".into(),
+                similar: "paypal".into()
+            },
+        ],
+        warnings
+    );
+    assert_eq!(Span::new(7, 14), parser.warnings()[0].context().span());
+}
+
+fn lossless_source(input: &str) -> String {
+    let tokens = TokenParser::parse_lossless(&mut SourceMap::default(), input.to_string()).unwrap();
+
+    tokens
+        .iter()
+        .map(|token| &input[Range::from(token.full_span())])
+        .collect()
+}
+
+#[test]
+fn lossless_tokens_rebuild_the_input() {
+    let inputs = [
+        "",
+        "   \n\t ",
+        "// only a comment\n/* and a block */",
+        "let a = 5; // five\n\n  /* nested /* block */ */ a + 2\t\r\n",
+        "/// doc\nfn f() {\n    return $\"{a} // {b}\";\n}\n   ",
+        "a/**/b//c",
+    ];
+
+    for input in inputs {
+        assert_eq!(input, lossless_source(input));
+    }
+}
+
+#[test]
+fn trailing_trivia_stops_at_newline() {
+    let input = "a // first\n  b";
+    let tokens = TokenParser::parse_lossless(&mut SourceMap::default(), input.to_string()).unwrap();
+
+    assert_eq!(3, tokens.len());
+    assert_eq!(
+        &[
+            Trivia::new(TriviaKind::Whitespace, Span::new(1, 2)),
+            Trivia::new(TriviaKind::LineComment, Span::new(2, 10)),
+        ],
+        tokens[0].trailing_trivia()
+    );
+    assert_eq!(
+        &[
+            Trivia::new(TriviaKind::Newline, Span::new(10, 11)),
+            Trivia::new(TriviaKind::Whitespace, Span::new(11, 13)),
+        ],
+        tokens[1].leading_trivia()
+    );
+    assert_eq!(TokenVariant::EndOfFile, *tokens[2].variant());
+    assert_eq!(Span::new(14, 14), tokens[2].span());
+}
+
+#[test]
+fn default_mode_drops_trivia() {
+    let tokens = TokenParser::parse(&mut SourceMap::default(), "a // a\n b ".to_string()).unwrap();
+
+    assert_eq!(
+        vec![
+            token(TokenVariant::Identifier("a".into()), 1, 1, 0..1),
+            token(TokenVariant::Identifier("b".into()), 2, 2, 8..9),
+        ],
+        tokens
+    );
+}
+
+#[test]
+fn invalid_input_becomes_error_tokens() {
+    let input = "a # \"b\\q\" + 0x";
+
+    let lexed = TokenParser::lex(&mut SourceMap::default(), input.into());
+
+    assert_eq!(
+        &[
+            token(TokenVariant::Identifier("a".into()), 1, 1, 0..1),
+            token(TokenVariant::Error, 1, 3, 2..3),
+            token(TokenVariant::Error, 1, 5, 4..9),
+            token(TokenVariant::Plus, 1, 11, 10..11),
+            token(TokenVariant::Error, 1, 13, 12..14),
+        ],
+        lexed.tokens()
+    );
+    let variants: Vec<&ParsingErrorVariant> =
+        lexed.errors().iter().map(ParsingError::variant).collect();
+    assert_eq!(
+        vec![
+            &ParsingErrorVariant::UnexpectedCharacter('#'),
+            &ParsingErrorVariant::InvalidEscape('q'),
+            &ParsingErrorVariant::MissingDigits,
+        ],
+        variants
+    );
+}
+
+#[test]
+fn error_tokens_inside_interpolation() {
+    let input = "$\"{a # b}\" c";
+
+    let lexed = TokenParser::lex(&mut SourceMap::default(), input.into());
+
+    assert_eq!(
+        &[
+            token(
+                TokenVariant::FormattedString(vec![(
+                    "".into(),
+                    vec![
+                        token(TokenVariant::Identifier("a".into()), 1, 4, 3..4),
+                        token(TokenVariant::Error, 1, 6, 5..6),
+                        token(TokenVariant::Identifier("b".into()), 1, 8, 7..8),
+                    ]
+                )]),
+                1,
+                1,
+                0..10
+            ),
+            token(TokenVariant::Identifier("c".into()), 1, 12, 11..12),
+        ],
+        lexed.tokens()
+    );
+    assert_eq!(1, lexed.errors().len());
+}
+
+#[test]
+fn lossless_with_error_tokens() {
+    let input = "a # /* b";
+
+    let tokens = TokenParser::new_lossless(FileId::default(), input)
+        .finish()
+        .into_parts()
+        .0;
+
+    let source: String = tokens
+        .iter()
+        .map(|token| &input[Range::from(token.full_span())])
+        .collect();
+    assert_eq!(input, source);
+}
+
+fn assert_relex_matches_full_lex(input: &str, edit: TextEdit, lossless: bool) {
+    let mut source_map = SourceMap::default();
+    let file = source_map.add_snippet(input.into());
+    let previous = if lossless {
+        TokenParser::new_lossless(file, source_map.text(file)).finish()
+    } else {
+        TokenParser::new(file, source_map.text(file)).finish()
+    };
+
+    let relexed = TokenParser::relex(&mut source_map, previous, &edit);
+
+    let text = source_map.text(file);
+    let lexed = if lossless {
+        TokenParser::new_lossless(file, text).finish()
+    } else {
+        TokenParser::new(file, text).finish()
+    };
+    let errors = |lexed: &LexedTokens| -> Vec<(String, BriseContext)> {
+        lexed
+            .errors()
+            .iter()
+            .map(|error| (error.variant().to_string(), error.context().clone()))
+            .collect()
+    };
+
+    let warnings = |lexed: &LexedTokens| -> Vec<(String, BriseContext)> {
+        lexed
+            .warnings()
+            .iter()
+            .map(|warning| (warning.variant().to_string(), warning.context().clone()))
+            .collect()
+    };
+
+    assert_eq!(lexed.tokens(), relexed.tokens(), "{edit:?} on {input:?}");
+    assert_eq!(errors(&lexed), errors(&relexed), "{edit:?} on {input:?}");
+    assert_eq!(
+        warnings(&lexed),
+        warnings(&relexed),
+        "{edit:?} on {input:?}"
+    );
+}
+
+#[test]
+fn relex_matches_full_lex() {
+    let inputs = [
+        "let é = 1.5; // a\r\n/* b */ c(\"d\\n\", $\"{e + 0x1}\")\n/// f\r\ng",
+        // Closing the comment turns the error token into trivia of the `;`
+        "let a = 1; /* note\nlet b = 2;",
+        // Byte order marks and shebangs are only trivia at the start
+        "\u{feff}#!x\na \u{feff}",
+    ];
+    let replacements = [
+        "", "a", " ", "\n", "\"", "/", "*", "*/", " */", "{", "}", ".", "1", "$\"", "r#", "#",
+        "\u{feff}",
+    ];
+
+    for input in inputs {
+        let boundaries: Vec<usize> = input
+            .char_indices()
+            .map(|(offset, _)| offset)
+            .chain(Some(input.len()))
+            .collect();
+
+        for (index, &start) in boundaries.iter().enumerate() {
+            for &end in boundaries[index..].iter().take(3) {
+                for replacement in replacements {
+                    for lossless in [false, true] {
+                        let edit = TextEdit::new(start..end, replacement);
+                        assert_relex_matches_full_lex(input, edit, lossless);
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn relex_moves_tokens_after_the_edit() {
+    let input = "a\nbb + c\nd";
+    let mut source_map = SourceMap::default();
+    let previous = TokenParser::lex(&mut source_map, input.into());
+
+    let relexed = TokenParser::relex(&mut source_map, previous, &TextEdit::new(2..4, "x\ny"));
+
+    assert_eq!(
+        &[
+            token(TokenVariant::Identifier("a".into()), 1, 1, 0..1),
+            token(TokenVariant::Identifier("x".into()), 2, 1, 2..3),
+            token(TokenVariant::Identifier("y".into()), 3, 1, 4..5),
+            token(TokenVariant::Plus, 3, 3, 6..7),
+            token(TokenVariant::Identifier("c".into()), 3, 5, 8..9),
+            token(TokenVariant::Identifier("d".into()), 4, 1, 10..11),
+        ],
+        relexed.tokens()
+    );
+}
+
+#[test]
+fn parse_raw_strings() {
+    let inputs = [
+        (r#"r"C:\path\n""#, r"C:\path\n"),
+        (r##"r#"say "hi""#"##, r#"say "hi""#),
+        (r###"r##"a "# b"##"###, r##"a "# b"##),
+        ("r\"\"", ""),
+    ];
+
+    for (input, text) in inputs {
+        let tokens = TokenParser::parse(&mut SourceMap::default(), input.into()).unwrap();
+
+        assert_eq!(
+            vec![token(
+                TokenVariant::String(text.into()),
+                1,
+                1,
+                0..input.len()
+            )],
+            tokens,
+            "{input}"
+        );
+    }
+}
+
+#[test]
+fn raw_string_errors() {
+    let inputs = [
+        (
+            "r#\"never closed\"",
+            ParsingErrorVariant::UnterminatedString,
+        ),
+        ("r## a", ParsingErrorVariant::MissingRawStringQuote),
+    ];
+
+    for (input, variant) in inputs {
+        let errors = lex_errors(input);
+
+        assert_eq!(1, errors.len(), "{input}");
+        assert_eq!(&variant, errors[0].variant(), "{input}");
+    }
+}
+
+#[test]
+fn multiline_string_strips_indentation() {
+    let input = "a = \"\"\"\n    SELECT *\n      FROM t\n\n    WHERE \\\"x\\\"\n    \"\"\" b";
+
+    let tokens = TokenParser::parse(&mut SourceMap::default(), input.into()).unwrap();
 
     let expected_tokens = vec![
-        token(TokenVariant::Identifier("myfunction".into()), 1, 1),
-        token(TokenVariant::LeftParen, 1, 11),
-        token(TokenVariant::Identifier("a".into()), 1, 12),
-        token(TokenVariant::Comma, 1, 13),
-        token(TokenVariant::Identifier("b".into()), 1, 15),
-        token(TokenVariant::RightParen, 1, 16),
-        token(TokenVariant::Semicolon, 1, 17),
+        token(TokenVariant::Identifier("a".into()), 1, 1, 0..1),
+        token(TokenVariant::Equal, 1, 3, 2..3),
+        token(
+            TokenVariant::String("SELECT *\n  FROM t\n\nWHERE \"x\"".into()),
+            1,
+            5,
+            4..58,
+        ),
+        token(TokenVariant::Identifier("b".into()), 6, 9, 59..60),
     ];
 
     assert_eq!(expected_tokens, tokens);
 }
+
+#[test]
+fn multiline_string_edge_cases() {
+    let inputs = [
+        ("\"\"\"one line\"\"\"", "one line"),
+        ("\"\"\"\n\tkeep\n\t  nested\n\t\"\"\"", "keep\n  nested"),
+        ("\"\"\"\n  a\n  \"\"\" ", "a"),
+        ("\"\"\"\n  \\n  a\n  \"\"\"", "\n  a"),
+        ("\"\"\"\"\"\"", ""),
+    ];
+
+    for (input, text) in inputs {
+        let tokens = TokenParser::parse(&mut SourceMap::default(), input.into()).unwrap();
+
+        assert_eq!(1, tokens.len(), "{input}");
+        assert_eq!(
+            &TokenVariant::String(text.into()),
+            tokens[0].variant(),
+            "{input}"
+        );
+    }
+}
+
+#[test]
+fn columns_match_the_line_index() {
+    let input =
+        "\u{feff}let é = \"😀\" + $\"{字}\";\r\n  r#\"x\"# /* ü */ e\u{301}\n\"\"\"\n  a\n\"\"\" b";
+    let index = LineIndex::new(input);
+
+    fn check(tokens: &[Token], input: &str, index: &LineIndex) {
+        for token in tokens {
+            let context = token.context();
+
+            assert_eq!(
+                index.location(input, context.span().start(), ColumnUnit::Char),
+                (context.line(), context.col()),
+                "{token:?}"
+            );
+            if let TokenVariant::FormattedString(parts) = token.variant() {
+                for (_, tokens) in parts {
+                    check(tokens, input, index);
+                }
+            }
+        }
+    }
+
+    let tokens = TokenParser::parse(&mut SourceMap::default(), input.into()).unwrap();
+    assert_eq!(11, tokens.len());
+    check(&tokens, input, &index);
+}
+
+#[test]
+fn tokens_serde_round_trip() {
+    let input = "let a = $\"{b}\" + 1.5; // c\n/// d\nr#\"e\"# @";
+    let (tokens, _, _) = TokenParser::new_lossless(FileId::default(), input)
+        .finish()
+        .into_parts();
+
+    let json = serde_json::to_string(&tokens).unwrap();
+    let read: Vec<Token> = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(tokens, read);
+}
+
+#[test]
+fn string_and_doc_comment_serde_round_trip() {
+    let tokens =
+        TokenParser::parse(&mut SourceMap::default(), "/// d\n\"e\" r#\"f\"#".into()).unwrap();
+
+    let json = serde_json::to_value(&tokens).unwrap();
+    let read: Vec<Token> = serde_json::from_value(json.clone()).unwrap();
+
+    assert_eq!(tokens, read);
+    assert_eq!(
+        serde_json::json!({ "DocComment": " d" }),
+        json[0]["variant"]
+    );
+    assert_eq!(serde_json::json!({ "String": "e" }), json[1]["variant"]);
+    assert_eq!(serde_json::json!({ "String": "f" }), json[2]["variant"]);
+}
+
+#[test]
+fn infinite_float_serde_round_trip() {
+    let tokens = vec![token(TokenVariant::Float(f64::INFINITY), 1, 1, 0..5)];
+
+    let json = serde_json::to_value(&tokens).unwrap();
+    let read: Vec<Token> = serde_json::from_value(json.clone()).unwrap();
+
+    assert_eq!(tokens, read);
+    assert_eq!(serde_json::json!({ "Float": "inf" }), json[0]["variant"]);
+}
+
+#[test]
+fn tokens_json_shape() {
+    let tokens = TokenParser::parse(&mut SourceMap::default(), "a (".into()).unwrap();
+
+    assert_eq!(
+        serde_json::json!([
+            {
+                "variant": { "Identifier": "a" },
+                "context": { "line": 1, "col": 1, "span": { "start": 0, "end": 1 }, "file": 0 }
+            },
+            {
+                "variant": "LeftParen",
+                "context": { "line": 1, "col": 3, "span": { "start": 2, "end": 3 }, "file": 0 }
+            }
+        ]),
+        serde_json::to_value(&tokens).unwrap()
+    );
+}
+
+fn assert_prints_back(tokens: &[Token]) {
+    let printed = printer::TokenPrinter::print(tokens);
+    let lexed = TokenParser::parse(&mut SourceMap::default(), printed.clone())
+        .unwrap_or_else(|error| panic!("{printed:?} does not lex: {error}"));
+
+    assert!(
+        lexed.len() == tokens.len()
+            && lexed
+                .iter()
+                .zip(tokens)
+                .all(|(lexed, token)| lexed.same_variant(token)),
+        "{printed:?} lexes to {lexed:?}"
+    );
+}
+
+#[test]
+fn print_tokens_with_minimal_spacing() {
+    let tokens = [
+        TokenVariant::Let,
+        TokenVariant::Identifier("a".into()),
+        TokenVariant::Equal,
+        TokenVariant::Integer(1),
+        TokenVariant::Dot,
+        TokenVariant::Integer(5),
+        TokenVariant::Plus,
+        TokenVariant::Float(2.5),
+        TokenVariant::Slash,
+        TokenVariant::Slash,
+        TokenVariant::Identifier("r".into()),
+        TokenVariant::String("x\"\n\\".into()),
+        TokenVariant::Equal,
+        TokenVariant::EqualEqual,
+    ]
+    .map(|variant| token(variant, 1, 1, 0..0));
+
+    assert_eq!(
+        r#"let a=1. 5+2.5/ /r "x\"\n\\"= =="#,
+        printer::TokenPrinter::print(&tokens)
+    );
+    assert_prints_back(&tokens);
+}
+
+#[test]
+fn print_formatted_strings_and_doc_comments() {
+    let tokens = [
+        TokenVariant::DocComment(" doc".into()),
+        TokenVariant::FormattedString(vec![(
+            "{a}".into(),
+            vec![token(TokenVariant::Integer(1), 1, 1, 0..0)],
+        )]),
+        TokenVariant::Error,
+    ]
+    .map(|variant| token(variant, 1, 1, 0..0));
+
+    assert_eq!(
+        "/// doc\n$\"{{a}}{1}\"",
+        printer::TokenPrinter::print(&tokens)
+    );
+    assert_prints_back(&tokens[..2]);
+}
+
+/// Xorshift generator, enough to draw reproducible token sequences
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn below(&mut self, bound: usize) -> usize {
+        (self.next() % bound as u64) as usize
+    }
+
+    fn pick<'a, T>(&mut self, values: &'a [T]) -> &'a T {
+        &values[self.below(values.len())]
+    }
+
+    fn text(&mut self, chars: &[char], max_len: usize) -> String {
+        (0..self.below(max_len + 1))
+            .map(|_| *self.pick(chars))
+            .collect()
+    }
+}
+
+fn random_token(rng: &mut Rng, in_interpolation: bool) -> Token {
+    const PUNCTUATION: [fn() -> TokenVariant; 40] = [
+        || TokenVariant::LeftParen,
+        || TokenVariant::RightParen,
+        || TokenVariant::LeftBracket,
+        || TokenVariant::RightBracket,
+        || TokenVariant::Comma,
+        || TokenVariant::Dot,
+        || TokenVariant::Minus,
+        || TokenVariant::Plus,
+        || TokenVariant::Semicolon,
+        || TokenVariant::Slash,
+        || TokenVariant::Star,
+        || TokenVariant::StarStar,
+        || TokenVariant::Percent,
+        || TokenVariant::PlusEqual,
+        || TokenVariant::MinusEqual,
+        || TokenVariant::StarEqual,
+        || TokenVariant::SlashEqual,
+        || TokenVariant::PercentEqual,
+        || TokenVariant::DotDot,
+        || TokenVariant::DotDotEqual,
+        || TokenVariant::FatArrow,
+        || TokenVariant::ColonColon,
+        || TokenVariant::BarGreater,
+        || TokenVariant::Bang,
+        || TokenVariant::BangEqual,
+        || TokenVariant::Equal,
+        || TokenVariant::EqualEqual,
+        || TokenVariant::Greater,
+        || TokenVariant::GreaterEqual,
+        || TokenVariant::Less,
+        || TokenVariant::LessEqual,
+        || TokenVariant::AmpersandAmpersand,
+        || TokenVariant::BarBar,
+        || TokenVariant::BangRightChevron,
+        || TokenVariant::QuestionMark,
+        || TokenVariant::Colon,
+        || TokenVariant::RightArrow,
+        || TokenVariant::Return,
+        || TokenVariant::BriseSelf,
+        || TokenVariant::True,
+    ];
+    const STRING_CHARS: [char; 8] = ['a', ' ', '"', '\\', '{', '}', '\n', 'é'];
+
+    let variant = match rng.below(10) {
+        0..=3 => PUNCTUATION[rng.below(PUNCTUATION.len())](),
+        4 => TokenVariant::Identifier((*rng.pick(&["a", "r", "e", "é", "x1", "_"])).into()),
+        5 => TokenVariant::Integer(rng.below(1000) as i64),
+        6 => TokenVariant::Float(rng.below(100_000) as f64 / 64.0),
+        7 => TokenVariant::String(rng.text(&STRING_CHARS, 4).into()),
+        8 if !in_interpolation => {
+            let mut parts: Vec<_> = (0..rng.below(3))
+                .map(|_| {
+                    let tokens = (0..rng.below(3) + 1)
+                        .map(|_| random_token(rng, true))
+                        .collect();
+                    (rng.text(&STRING_CHARS, 3).into(), tokens)
+                })
+                .collect();
+            // The lexer leaves out an empty text after the last interpolation
+            let text = rng.text(&STRING_CHARS, 3);
+            if parts.is_empty() || !text.is_empty() {
+                parts.push((text.into(), vec![]));
+            }
+
+            TokenVariant::FormattedString(parts)
+        }
+        9 if !in_interpolation => TokenVariant::DocComment(rng.text(&['a', ' ', '\r'], 4).into()),
+        _ => TokenVariant::Let,
+    };
+
+    token(variant, 1, 1, 0..0)
+}
+
+#[test]
+fn printed_tokens_lex_back() {
+    let mut rng = Rng(0x2545_f491_4f6c_dd1d);
+
+    for _ in 0..500 {
+        let tokens: Vec<Token> = (0..rng.below(12))
+            .map(|_| random_token(&mut rng, false))
+            .collect();
+
+        assert_prints_back(&tokens);
+    }
+}
+
+#[test]
+fn skip_shebang_and_byte_order_mark() {
+    for input in [
+        "#!/usr/bin/env brise\nlet",
+        "\u{feff}#!/usr/bin/env brise\r\nlet",
+    ] {
+        let tokens = TokenParser::parse(&mut SourceMap::default(), input.into()).unwrap();
+        let start = input.len() - 3;
+
+        assert_eq!(
+            vec![token(TokenVariant::Let, 2, 1, start..input.len())],
+            tokens
+        );
+        assert_eq!(input, lossless_source(input));
+    }
+
+    let input = "\u{feff}let";
+    let tokens = TokenParser::parse(&mut SourceMap::default(), input.into()).unwrap();
+    assert_eq!(vec![token(TokenVariant::Let, 1, 1, 3..6)], tokens);
+
+    // Only the first line may be a shebang
+    assert_eq!(1, lex_errors("a\n#!b").len());
+}
+
+#[test]
+fn carriage_return_line_feed_is_one_newline() {
+    let input = "a \r\n/// b\r\n\"\"\"\r\n  c\r\n  d\r\n\"\"\"\r\n\"e\"\r\n";
+    let tokens = TokenParser::parse(&mut SourceMap::default(), input.into()).unwrap();
+
+    assert_eq!(
+        vec![
+            token(TokenVariant::Identifier("a".into()), 1, 1, 0..1),
+            token(TokenVariant::DocComment(" b".into()), 2, 1, 4..9),
+            token(TokenVariant::String("c\nd".into()), 3, 1, 11..29),
+        ],
+        tokens[..3]
+    );
+
+    let (lines, trivia): (Vec<_>, Vec<_>) =
+        TokenParser::parse_lossless(&mut SourceMap::default(), "a\r\n\rb".into())
+            .unwrap()
+            .iter()
+            .map(|token| {
+                (
+                    token.context().line().get(),
+                    token.leading_trivia().to_vec(),
+                )
+            })
+            .unzip();
+    assert_eq!(vec![1, 2, 2], lines);
+    assert_eq!(
+        vec![TriviaKind::Newline, TriviaKind::Whitespace],
+        trivia[1].iter().map(Trivia::kind).collect::<Vec<_>>()
+    );
+}
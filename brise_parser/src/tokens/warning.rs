@@ -0,0 +1,43 @@
+use brise_token::{BriseContext, RawString, SourceMap};
+use thiserror::Error;
+
+#[derive(Debug, PartialEq, Error)]
+pub enum ParsingWarningVariant {
+    #[error("Identifier `{0}` mixes characters from several scripts")]
+    MixedScriptIdentifier(RawString),
+    #[error("Identifier `{identifier}` can be confused with `{similar}`")]
+    ConfusableIdentifier {
+        identifier: RawString,
+        similar: RawString,
+    },
+}
+
+/// Lint-style diagnostic, the input is still lexed
+#[derive(Debug, Error)]
+#[error("{context} warning: {variant}")]
+pub struct ParsingWarning {
+    variant: ParsingWarningVariant,
+    context: BriseContext,
+}
+
+impl ParsingWarning {
+    pub fn new(variant: ParsingWarningVariant, context: BriseContext) -> Self {
+        Self { variant, context }
+    }
+
+    pub fn variant(&self) -> &ParsingWarningVariant {
+        &self.variant
+    }
+
+    pub fn context(&self) -> &BriseContext {
+        &self.context
+    }
+
+    pub fn into_parts(self) -> (ParsingWarningVariant, BriseContext) {
+        (self.variant, self.context)
+    }
+
+    pub fn render(&self, source_map: &SourceMap) -> String {
+        source_map.render(&self.context, format_args!("warning: {}", self.variant))
+    }
+}
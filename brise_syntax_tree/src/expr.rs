@@ -1,33 +1,109 @@
-use std::rc::Rc;
+use std::{hash::Hash, rc::Rc};
 
 use binary::BinaryExpr;
+use brise_token::{BriseContext, Span};
+use call::CallExpr;
+use error::ErrorExpr;
+use field::FieldExpr;
+use grouping::GroupingExpr;
 use identifier::Identifier;
+use index::IndexExpr;
 use literal::Literal;
 use unary::UnaryExpr;
 
+use crate::node_id::NodeId;
+
 pub mod binary;
+pub mod call;
+pub mod error;
+pub mod field;
+pub mod grouping;
 pub mod identifier;
+pub mod index;
 pub mod literal;
 pub mod unary;
 
-#[derive(Debug, PartialEq, Eq, Hash, Clone)]
-pub struct Expr(Rc<ExprVariant>);
+/// Expressions are compared and hashed by structure, the id is left out.
+/// A clone is the same node and keeps the id.
+#[derive(Debug, Clone)]
+pub struct Expr {
+    id: NodeId,
+    variant: Rc<ExprVariant>,
+}
 
 impl Expr {
     pub fn new(variant: ExprVariant) -> Self {
-        Self(Rc::new(variant))
+        Self {
+            id: NodeId::next(),
+            variant: Rc::new(variant),
+        }
+    }
+
+    pub fn id(&self) -> NodeId {
+        self.id
     }
 
     pub fn variant(&self) -> &ExprVariant {
-        &self.0
+        &self.variant
+    }
+
+    /// Range covered by the whole expression
+    pub fn span(&self) -> Span {
+        match self.variant() {
+            ExprVariant::Binary(binary) => binary.left().span().to(binary.right().span()),
+            ExprVariant::Unary(unary) => unary.operator().span().to(unary.expr().span()),
+            ExprVariant::Grouping(grouping) => grouping.span(),
+            ExprVariant::Literal(literal) => literal.span(),
+            ExprVariant::Identifier(identifier) => identifier.span(),
+            ExprVariant::Call(call) => call.callee().span().to(call.span()),
+            ExprVariant::Field(field) => field.object().span().to(field.name().span()),
+            ExprVariant::Index(index) => index.object().span().to(index.span()),
+            ExprVariant::Error(error) => error.span(),
+        }
+    }
+
+    /// Context covering the whole expression, its line and column are the ones
+    /// of the start of the expression
+    pub fn context(&self) -> BriseContext {
+        match self.variant() {
+            ExprVariant::Binary(binary) => binary.left().context().to(&binary.right().context()),
+            ExprVariant::Unary(unary) => unary.operator().context().to(&unary.expr().context()),
+            ExprVariant::Grouping(grouping) => grouping.context().clone(),
+            ExprVariant::Literal(literal) => literal.context().clone(),
+            ExprVariant::Identifier(identifier) => identifier.context().clone(),
+            ExprVariant::Call(call) => call.callee().context().to(call.context()),
+            ExprVariant::Field(field) => field.object().context().to(field.name().context()),
+            ExprVariant::Index(index) => index.object().context().to(index.context()),
+            ExprVariant::Error(error) => error.context().clone(),
+        }
+    }
+}
+
+impl PartialEq for Expr {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.variant, &other.variant) || self.variant == other.variant
+    }
+}
+
+impl Eq for Expr {}
+
+impl Hash for Expr {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.variant.hash(state);
     }
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug, PartialEq, Eq, Hash, Clone)]
 pub enum ExprVariant {
     Binary(BinaryExpr),
     Unary(UnaryExpr),
-    Grouping(Expr),
+    Grouping(GroupingExpr),
     Literal(Literal),
     Identifier(Identifier),
+    Call(CallExpr),
+    Field(FieldExpr),
+    Index(IndexExpr),
+    /// Stands for input that failed to parse, only in trees parsed with errors
+    Error(ErrorExpr),
 }
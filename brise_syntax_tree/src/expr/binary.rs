@@ -1,9 +1,10 @@
-use brise_token::{BriseContext, Token, TokenVariant};
+use brise_token::{BriseContext, Span, Token, TokenVariant};
 
 use crate::error::TokenConversionError;
 
 use super::{Expr, ExprVariant};
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
 pub enum BinaryOperatorVariant {
     EqualEqual,
@@ -16,10 +17,23 @@ pub enum BinaryOperatorVariant {
     Minus,
     Star,
     Slash,
+    Percent,
+    StarStar,
     Or,
     And,
+    DotDot,
+    DotDotEqual,
+    /// `|>`
+    Pipe,
+    Equal,
+    PlusEqual,
+    MinusEqual,
+    StarEqual,
+    SlashEqual,
+    PercentEqual,
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug, PartialEq, Eq, Hash, Clone)]
 pub struct BinaryOperator {
     variant: BinaryOperatorVariant,
@@ -38,6 +52,10 @@ impl BinaryOperator {
     pub fn context(&self) -> &BriseContext {
         &self.context
     }
+
+    pub fn span(&self) -> Span {
+        self.context.span()
+    }
 }
 
 impl TryFrom<Token> for BinaryOperator {
@@ -55,8 +73,19 @@ impl TryFrom<Token> for BinaryOperator {
             TokenVariant::Minus => BinaryOperatorVariant::Minus,
             TokenVariant::Star => BinaryOperatorVariant::Star,
             TokenVariant::Slash => BinaryOperatorVariant::Slash,
+            TokenVariant::Percent => BinaryOperatorVariant::Percent,
+            TokenVariant::StarStar => BinaryOperatorVariant::StarStar,
             TokenVariant::BarBar => BinaryOperatorVariant::Or,
             TokenVariant::AmpersandAmpersand => BinaryOperatorVariant::And,
+            TokenVariant::DotDot => BinaryOperatorVariant::DotDot,
+            TokenVariant::DotDotEqual => BinaryOperatorVariant::DotDotEqual,
+            TokenVariant::BarGreater => BinaryOperatorVariant::Pipe,
+            TokenVariant::Equal => BinaryOperatorVariant::Equal,
+            TokenVariant::PlusEqual => BinaryOperatorVariant::PlusEqual,
+            TokenVariant::MinusEqual => BinaryOperatorVariant::MinusEqual,
+            TokenVariant::StarEqual => BinaryOperatorVariant::StarEqual,
+            TokenVariant::SlashEqual => BinaryOperatorVariant::SlashEqual,
+            TokenVariant::PercentEqual => BinaryOperatorVariant::PercentEqual,
             _ => return Err(TokenConversionError::BinaryOperator(value)),
         };
 
@@ -67,6 +96,7 @@ impl TryFrom<Token> for BinaryOperator {
     }
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug, PartialEq, Eq, Hash, Clone)]
 pub struct BinaryExpr {
     left: Expr,
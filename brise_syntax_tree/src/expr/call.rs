@@ -0,0 +1,45 @@
+use brise_token::{BriseContext, Span};
+
+use super::{Expr, ExprVariant};
+
+/// `f(a, b)`
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct CallExpr {
+    callee: Expr,
+    args: Vec<Expr>,
+    /// Covers the parentheses
+    context: BriseContext,
+}
+
+impl CallExpr {
+    pub fn new(callee: Expr, args: Vec<Expr>, context: BriseContext) -> Self {
+        Self {
+            callee,
+            args,
+            context,
+        }
+    }
+
+    pub fn callee(&self) -> &Expr {
+        &self.callee
+    }
+
+    pub fn args(&self) -> &[Expr] {
+        &self.args
+    }
+
+    pub fn context(&self) -> &BriseContext {
+        &self.context
+    }
+
+    pub fn span(&self) -> Span {
+        self.context.span()
+    }
+}
+
+impl From<CallExpr> for Expr {
+    fn from(value: CallExpr) -> Self {
+        Self::new(ExprVariant::Call(value))
+    }
+}
@@ -0,0 +1,31 @@
+use brise_token::{BriseContext, Span};
+
+use super::{Expr, ExprVariant};
+
+/// Placeholder for input that could not be parsed as an expression,
+/// the parser reports the error apart from the tree
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct ErrorExpr {
+    context: BriseContext,
+}
+
+impl ErrorExpr {
+    pub fn new(context: BriseContext) -> Self {
+        Self { context }
+    }
+
+    pub fn context(&self) -> &BriseContext {
+        &self.context
+    }
+
+    pub fn span(&self) -> Span {
+        self.context.span()
+    }
+}
+
+impl From<ErrorExpr> for Expr {
+    fn from(value: ErrorExpr) -> Self {
+        Self::new(ExprVariant::Error(value))
+    }
+}
@@ -0,0 +1,29 @@
+use super::{identifier::Identifier, Expr, ExprVariant};
+
+/// `a.b`
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct FieldExpr {
+    object: Expr,
+    name: Identifier,
+}
+
+impl FieldExpr {
+    pub fn new(object: Expr, name: Identifier) -> Self {
+        Self { object, name }
+    }
+
+    pub fn object(&self) -> &Expr {
+        &self.object
+    }
+
+    pub fn name(&self) -> &Identifier {
+        &self.name
+    }
+}
+
+impl From<FieldExpr> for Expr {
+    fn from(value: FieldExpr) -> Self {
+        Self::new(ExprVariant::Field(value))
+    }
+}
@@ -0,0 +1,35 @@
+use brise_token::{BriseContext, Span};
+
+use super::{Expr, ExprVariant};
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct GroupingExpr {
+    expr: Expr,
+    /// Covers the parentheses
+    context: BriseContext,
+}
+
+impl GroupingExpr {
+    pub fn new(expr: Expr, context: BriseContext) -> Self {
+        Self { expr, context }
+    }
+
+    pub fn expr(&self) -> &Expr {
+        &self.expr
+    }
+
+    pub fn context(&self) -> &BriseContext {
+        &self.context
+    }
+
+    pub fn span(&self) -> Span {
+        self.context.span()
+    }
+}
+
+impl From<GroupingExpr> for Expr {
+    fn from(value: GroupingExpr) -> Self {
+        Self::new(ExprVariant::Grouping(value))
+    }
+}
@@ -1,7 +1,8 @@
-use brise_token::{BriseContext, RawString};
+use brise_token::{BriseContext, RawString, Span};
 
 use super::{Expr, ExprVariant};
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug, PartialEq, Eq, Hash, Clone)]
 pub struct Identifier {
     identity: RawString,
@@ -20,6 +21,10 @@ impl Identifier {
     pub fn context(&self) -> &BriseContext {
         &self.context
     }
+
+    pub fn span(&self) -> Span {
+        self.context.span()
+    }
 }
 
 impl From<Identifier> for Expr {
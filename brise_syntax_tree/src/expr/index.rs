@@ -0,0 +1,45 @@
+use brise_token::{BriseContext, Span};
+
+use super::{Expr, ExprVariant};
+
+/// `a[b]`
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct IndexExpr {
+    object: Expr,
+    index: Expr,
+    /// Covers the brackets
+    context: BriseContext,
+}
+
+impl IndexExpr {
+    pub fn new(object: Expr, index: Expr, context: BriseContext) -> Self {
+        Self {
+            object,
+            index,
+            context,
+        }
+    }
+
+    pub fn object(&self) -> &Expr {
+        &self.object
+    }
+
+    pub fn index(&self) -> &Expr {
+        &self.index
+    }
+
+    pub fn context(&self) -> &BriseContext {
+        &self.context
+    }
+
+    pub fn span(&self) -> Span {
+        self.context.span()
+    }
+}
+
+impl From<IndexExpr> for Expr {
+    fn from(value: IndexExpr) -> Self {
+        Self::new(ExprVariant::Index(value))
+    }
+}
@@ -1,15 +1,14 @@
-use std::{
-    hash::Hash,
-    sync::atomic::{AtomicUsize, Ordering},
-};
+use std::hash::Hash;
 
-use brise_token::{BriseContext, RawString};
+use brise_token::{BriseContext, RawString, Span};
 
 use super::{Expr, ExprVariant};
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug, PartialEq, Eq, Hash, Clone)]
 pub enum LiteralVariant {
-    Number(NumberLiteral),
+    Integer(i64),
+    Float(NumberLiteral),
     String(RawString),
     FormattedString(Vec<Expr>),
     True,
@@ -17,37 +16,35 @@ pub enum LiteralVariant {
     Unknown,
 }
 
+/// Compared and hashed by bit pattern: `0.0` and `-0.0` differ,
+/// and every NaN is equal to every other NaN
 #[derive(Debug, Clone, Copy)]
 pub struct NumberLiteral {
     value: f64,
-    /// The id is there to hash the value,
-    /// each number literal should have a different id
-    id: usize,
 }
 
 impl NumberLiteral {
     pub fn new(value: f64) -> Self {
-        static COUNTER: AtomicUsize = AtomicUsize::new(0);
-
-        Self {
-            value,
-            id: COUNTER.fetch_add(1, Ordering::Relaxed),
-        }
-    }
-
-    /// This method should only be used in tests
-    pub fn new_with_custom_id(value: f64, id: usize) -> Self {
-        Self { value, id }
+        Self { value }
     }
 
     pub fn value(&self) -> f64 {
         self.value
     }
+
+    /// NaNs have several bit patterns, they all get the one of `f64::NAN`
+    fn bits(&self) -> u64 {
+        if self.value.is_nan() {
+            f64::NAN.to_bits()
+        } else {
+            self.value.to_bits()
+        }
+    }
 }
 
 impl PartialEq for NumberLiteral {
     fn eq(&self, other: &Self) -> bool {
-        self.id == other.id
+        self.bits() == other.bits()
     }
 }
 
@@ -55,10 +52,11 @@ impl Eq for NumberLiteral {}
 
 impl Hash for NumberLiteral {
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
-        self.id.hash(state);
+        self.bits().hash(state);
     }
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug, PartialEq, Eq, Hash, Clone)]
 pub struct Literal {
     variant: LiteralVariant,
@@ -77,6 +75,10 @@ impl Literal {
     pub fn context(&self) -> &BriseContext {
         &self.context
     }
+
+    pub fn span(&self) -> Span {
+        self.context.span()
+    }
 }
 
 impl From<Literal> for Expr {
@@ -1,15 +1,17 @@
-use brise_token::{BriseContext, Token, TokenVariant};
+use brise_token::{BriseContext, Span, Token, TokenVariant};
 
 use crate::error::TokenConversionError;
 
 use super::{Expr, ExprVariant};
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
 pub enum UnaryOperatorVariant {
     Bang,
     Minus,
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug, PartialEq, Eq, Hash, Clone)]
 pub struct UnaryOperator {
     variant: UnaryOperatorVariant,
@@ -28,6 +30,10 @@ impl UnaryOperator {
     pub fn context(&self) -> &BriseContext {
         &self.context
     }
+
+    pub fn span(&self) -> Span {
+        self.context.span()
+    }
 }
 
 impl TryFrom<Token> for UnaryOperator {
@@ -44,6 +50,7 @@ impl TryFrom<Token> for UnaryOperator {
     }
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug, PartialEq, Eq, Hash, Clone)]
 pub struct UnaryExpr {
     operator: UnaryOperator,
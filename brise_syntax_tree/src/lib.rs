@@ -1,2 +1,7 @@
 pub mod error;
 pub mod expr;
+pub mod node_id;
+pub mod program;
+#[cfg(feature = "serde")]
+mod serialization;
+pub mod stmt;
@@ -0,0 +1,19 @@
+use std::sync::atomic::{AtomicU32, Ordering};
+
+/// Identity of a node of the syntax tree, to key side tables by node. Two nodes
+/// built separately never share an id, even when they are structurally equal.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+pub struct NodeId(u32);
+
+impl NodeId {
+    /// Every call gives a new id
+    pub fn next() -> Self {
+        static COUNTER: AtomicU32 = AtomicU32::new(0);
+
+        Self(COUNTER.fetch_add(1, Ordering::Relaxed))
+    }
+
+    pub fn index(self) -> u32 {
+        self.0
+    }
+}
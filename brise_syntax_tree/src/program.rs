@@ -0,0 +1,34 @@
+use brise_token::{BriseContext, Span};
+
+use crate::stmt::Stmt;
+
+/// Every statement of a source file
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct Program {
+    stmts: Vec<Stmt>,
+    /// Covers the whole source
+    context: BriseContext,
+}
+
+impl Program {
+    pub fn new(stmts: Vec<Stmt>, context: BriseContext) -> Self {
+        Self { stmts, context }
+    }
+
+    pub fn stmts(&self) -> &[Stmt] {
+        &self.stmts
+    }
+
+    pub fn context(&self) -> &BriseContext {
+        &self.context
+    }
+
+    pub fn span(&self) -> Span {
+        self.context.span()
+    }
+
+    pub fn into_stmts(self) -> Vec<Stmt> {
+        self.stmts
+    }
+}
@@ -0,0 +1,67 @@
+//! JSON shape of the syntax tree, with the `serde` feature enabled. The types coming
+//! from `brise_token` follow the shape documented there.
+//!
+//! - `Expr`: its variant, `{"Binary": {"left": ..., "operator": ..., "right": ...}}`.
+//!   The id is not written, an expression read back gets a new one. A sub-expression
+//!   shared by several nodes is written once per node, and is no longer shared once read back
+//! - `BinaryOperator` and `UnaryOperator`: `{"variant": "Plus", "context": ...}`
+//! - `UnaryExpr`: `{"operator": ..., "expr": ...}`
+//! - `GroupingExpr`: `{"expr": ..., "context": ...}`, the context covers the parentheses
+//! - `Identifier`: `{"identity": "a", "context": ...}`
+//! - `CallExpr`: `{"callee": ..., "args": [...], "context": ...}`, the context covers the parentheses
+//! - `FieldExpr`: `{"object": ..., "name": ...}` with an `Identifier` name
+//! - `IndexExpr`: `{"object": ..., "index": ..., "context": ...}`, the context covers the brackets
+//! - `ErrorExpr`: `{"context": ...}`, covers the input that failed to parse
+//! - `Literal`: `{"variant": ..., "context": ...}` with a variant like `"True"`,
+//!   `{"Integer": 1}`, `{"Float": 1.5}` or `{"FormattedString": [...exprs]}`. A float
+//!   out of the range of JSON numbers is a string, like in tokens
+//! - `Stmt`: its variant, like `Expr`, `{"Let": {"name": ..., "value": ..., "context": ...}}`
+//!   or `{"Break": {"context": ...}}`. A missing value is `null`
+//! - `IfStmt`: `{"condition": ..., "then_branch": ..., "else_branch": {"Block": ...}, "context": ...}`,
+//!   an `else if` is `{"If": ...}`
+//! - `Program`: `{"stmts": [...], "context": ...}`
+//!
+//! Renaming a variant or a field changes the shape, external tools depend on it.
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{
+    expr::{literal::NumberLiteral, Expr, ExprVariant},
+    stmt::{Stmt, StmtVariant},
+};
+
+impl Serialize for Expr {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.variant().serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Expr {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        ExprVariant::deserialize(deserializer).map(Expr::new)
+    }
+}
+
+impl Serialize for Stmt {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.variant().serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Stmt {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        StmtVariant::deserialize(deserializer).map(Stmt::new)
+    }
+}
+
+impl Serialize for NumberLiteral {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        brise_token::serialization::float::serialize(&self.value(), serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for NumberLiteral {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        brise_token::serialization::float::deserialize(deserializer).map(NumberLiteral::new)
+    }
+}
@@ -0,0 +1,105 @@
+use std::{hash::Hash, rc::Rc};
+
+use block::Block;
+use break_stmt::BreakStmt;
+use brise_token::{BriseContext, Span};
+use continue_stmt::ContinueStmt;
+use expr_stmt::ExprStmt;
+use fn_decl::FnDecl;
+use for_stmt::ForStmt;
+use if_stmt::IfStmt;
+use let_stmt::LetStmt;
+use loop_stmt::LoopStmt;
+use return_stmt::ReturnStmt;
+use while_stmt::WhileStmt;
+
+use crate::node_id::NodeId;
+
+pub mod block;
+pub mod break_stmt;
+pub mod continue_stmt;
+pub mod expr_stmt;
+pub mod fn_decl;
+pub mod for_stmt;
+pub mod if_stmt;
+pub mod let_stmt;
+pub mod loop_stmt;
+pub mod return_stmt;
+pub mod while_stmt;
+
+/// Statements are compared and hashed by structure, the id is left out,
+/// as for [`crate::expr::Expr`]
+#[derive(Debug, Clone)]
+pub struct Stmt {
+    id: NodeId,
+    variant: Rc<StmtVariant>,
+}
+
+impl Stmt {
+    pub fn new(variant: StmtVariant) -> Self {
+        Self {
+            id: NodeId::next(),
+            variant: Rc::new(variant),
+        }
+    }
+
+    pub fn id(&self) -> NodeId {
+        self.id
+    }
+
+    pub fn variant(&self) -> &StmtVariant {
+        &self.variant
+    }
+
+    /// Range covered by the whole statement
+    pub fn span(&self) -> Span {
+        self.context().span()
+    }
+
+    /// Context covering the whole statement
+    pub fn context(&self) -> &BriseContext {
+        match self.variant() {
+            StmtVariant::Let(stmt) => stmt.context(),
+            StmtVariant::Expr(stmt) => stmt.context(),
+            StmtVariant::Block(block) => block.context(),
+            StmtVariant::If(stmt) => stmt.context(),
+            StmtVariant::While(stmt) => stmt.context(),
+            StmtVariant::Loop(stmt) => stmt.context(),
+            StmtVariant::For(stmt) => stmt.context(),
+            StmtVariant::Break(stmt) => stmt.context(),
+            StmtVariant::Continue(stmt) => stmt.context(),
+            StmtVariant::Return(stmt) => stmt.context(),
+            StmtVariant::FnDecl(decl) => decl.context(),
+        }
+    }
+}
+
+impl PartialEq for Stmt {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.variant, &other.variant) || self.variant == other.variant
+    }
+}
+
+impl Eq for Stmt {}
+
+impl Hash for Stmt {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.variant.hash(state);
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub enum StmtVariant {
+    Let(LetStmt),
+    Expr(ExprStmt),
+    Block(Block),
+    If(IfStmt),
+    While(WhileStmt),
+    Loop(LoopStmt),
+    For(ForStmt),
+    Break(BreakStmt),
+    Continue(ContinueStmt),
+    Return(ReturnStmt),
+    FnDecl(FnDecl),
+}
@@ -0,0 +1,36 @@
+use brise_token::{BriseContext, Span};
+
+use super::{Stmt, StmtVariant};
+
+/// `{ let a = 1; a += 2; }`
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct Block {
+    stmts: Vec<Stmt>,
+    /// Covers the braces
+    context: BriseContext,
+}
+
+impl Block {
+    pub fn new(stmts: Vec<Stmt>, context: BriseContext) -> Self {
+        Self { stmts, context }
+    }
+
+    pub fn stmts(&self) -> &[Stmt] {
+        &self.stmts
+    }
+
+    pub fn context(&self) -> &BriseContext {
+        &self.context
+    }
+
+    pub fn span(&self) -> Span {
+        self.context.span()
+    }
+}
+
+impl From<Block> for Stmt {
+    fn from(value: Block) -> Self {
+        Self::new(StmtVariant::Block(value))
+    }
+}
@@ -0,0 +1,31 @@
+use brise_token::{BriseContext, Span};
+
+use super::{Stmt, StmtVariant};
+
+/// `break;`
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct BreakStmt {
+    /// Covers the semicolon
+    context: BriseContext,
+}
+
+impl BreakStmt {
+    pub fn new(context: BriseContext) -> Self {
+        Self { context }
+    }
+
+    pub fn context(&self) -> &BriseContext {
+        &self.context
+    }
+
+    pub fn span(&self) -> Span {
+        self.context.span()
+    }
+}
+
+impl From<BreakStmt> for Stmt {
+    fn from(value: BreakStmt) -> Self {
+        Self::new(StmtVariant::Break(value))
+    }
+}
@@ -0,0 +1,31 @@
+use brise_token::{BriseContext, Span};
+
+use super::{Stmt, StmtVariant};
+
+/// `continue;`
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct ContinueStmt {
+    /// Covers the semicolon
+    context: BriseContext,
+}
+
+impl ContinueStmt {
+    pub fn new(context: BriseContext) -> Self {
+        Self { context }
+    }
+
+    pub fn context(&self) -> &BriseContext {
+        &self.context
+    }
+
+    pub fn span(&self) -> Span {
+        self.context.span()
+    }
+}
+
+impl From<ContinueStmt> for Stmt {
+    fn from(value: ContinueStmt) -> Self {
+        Self::new(StmtVariant::Continue(value))
+    }
+}
@@ -0,0 +1,38 @@
+use brise_token::{BriseContext, Span};
+
+use crate::expr::Expr;
+
+use super::{Stmt, StmtVariant};
+
+/// Expression evaluated for its effects: `a += 1;`
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct ExprStmt {
+    expr: Expr,
+    /// Covers the semicolon
+    context: BriseContext,
+}
+
+impl ExprStmt {
+    pub fn new(expr: Expr, context: BriseContext) -> Self {
+        Self { expr, context }
+    }
+
+    pub fn expr(&self) -> &Expr {
+        &self.expr
+    }
+
+    pub fn context(&self) -> &BriseContext {
+        &self.context
+    }
+
+    pub fn span(&self) -> Span {
+        self.context.span()
+    }
+}
+
+impl From<ExprStmt> for Stmt {
+    fn from(value: ExprStmt) -> Self {
+        Self::new(StmtVariant::Expr(value))
+    }
+}
@@ -0,0 +1,66 @@
+use brise_token::{BriseContext, RawString, Span};
+
+use crate::expr::identifier::Identifier;
+
+use super::{block::Block, Stmt, StmtVariant};
+
+/// `fn add(a, b) { ... }`
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct FnDecl {
+    name: Identifier,
+    params: Vec<Identifier>,
+    body: Block,
+    /// Text of the `///` comments right before the function, one per line
+    doc: Vec<RawString>,
+    /// From `fn` to the end of the body, the doc comments are left out
+    context: BriseContext,
+}
+
+impl FnDecl {
+    pub fn new(
+        name: Identifier,
+        params: Vec<Identifier>,
+        body: Block,
+        doc: Vec<RawString>,
+        context: BriseContext,
+    ) -> Self {
+        Self {
+            name,
+            params,
+            body,
+            doc,
+            context,
+        }
+    }
+
+    pub fn name(&self) -> &Identifier {
+        &self.name
+    }
+
+    pub fn params(&self) -> &[Identifier] {
+        &self.params
+    }
+
+    pub fn body(&self) -> &Block {
+        &self.body
+    }
+
+    pub fn doc(&self) -> &[RawString] {
+        &self.doc
+    }
+
+    pub fn context(&self) -> &BriseContext {
+        &self.context
+    }
+
+    pub fn span(&self) -> Span {
+        self.context.span()
+    }
+}
+
+impl From<FnDecl> for Stmt {
+    fn from(value: FnDecl) -> Self {
+        Self::new(StmtVariant::FnDecl(value))
+    }
+}
@@ -0,0 +1,52 @@
+use brise_token::{BriseContext, Span};
+
+use crate::expr::{identifier::Identifier, Expr};
+
+use super::{block::Block, Stmt, StmtVariant};
+
+/// `for i: 0..10 { ... }`, the variable takes every value of the iterable
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct ForStmt {
+    variable: Identifier,
+    iterable: Expr,
+    body: Block,
+    context: BriseContext,
+}
+
+impl ForStmt {
+    pub fn new(variable: Identifier, iterable: Expr, body: Block, context: BriseContext) -> Self {
+        Self {
+            variable,
+            iterable,
+            body,
+            context,
+        }
+    }
+
+    pub fn variable(&self) -> &Identifier {
+        &self.variable
+    }
+
+    pub fn iterable(&self) -> &Expr {
+        &self.iterable
+    }
+
+    pub fn body(&self) -> &Block {
+        &self.body
+    }
+
+    pub fn context(&self) -> &BriseContext {
+        &self.context
+    }
+
+    pub fn span(&self) -> Span {
+        self.context.span()
+    }
+}
+
+impl From<ForStmt> for Stmt {
+    fn from(value: ForStmt) -> Self {
+        Self::new(StmtVariant::For(value))
+    }
+}
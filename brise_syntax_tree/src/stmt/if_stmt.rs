@@ -0,0 +1,75 @@
+use brise_token::{BriseContext, Span};
+
+use crate::expr::Expr;
+
+use super::{block::Block, Stmt, StmtVariant};
+
+/// `if a { ... } else if b { ... } else { ... }`
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct IfStmt {
+    condition: Expr,
+    then_branch: Block,
+    else_branch: Option<ElseBranch>,
+    /// From `if` to the end of the last branch
+    context: BriseContext,
+}
+
+/// What follows `else`
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub enum ElseBranch {
+    Block(Block),
+    If(Box<IfStmt>),
+}
+
+impl IfStmt {
+    pub fn new(
+        condition: Expr,
+        then_branch: Block,
+        else_branch: Option<ElseBranch>,
+        context: BriseContext,
+    ) -> Self {
+        Self {
+            condition,
+            then_branch,
+            else_branch,
+            context,
+        }
+    }
+
+    pub fn condition(&self) -> &Expr {
+        &self.condition
+    }
+
+    pub fn then_branch(&self) -> &Block {
+        &self.then_branch
+    }
+
+    pub fn else_branch(&self) -> Option<&ElseBranch> {
+        self.else_branch.as_ref()
+    }
+
+    pub fn context(&self) -> &BriseContext {
+        &self.context
+    }
+
+    pub fn span(&self) -> Span {
+        self.context.span()
+    }
+}
+
+impl ElseBranch {
+    pub fn context(&self) -> &BriseContext {
+        match self {
+            Self::Block(block) => block.context(),
+            Self::If(stmt) => stmt.context(),
+        }
+    }
+}
+
+impl From<IfStmt> for Stmt {
+    fn from(value: IfStmt) -> Self {
+        Self::new(StmtVariant::If(value))
+    }
+}
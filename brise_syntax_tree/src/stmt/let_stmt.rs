@@ -0,0 +1,47 @@
+use brise_token::{BriseContext, Span};
+
+use crate::expr::{identifier::Identifier, Expr};
+
+use super::{Stmt, StmtVariant};
+
+/// `let a = 1;`, the value may be left out: `let a;`
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct LetStmt {
+    name: Identifier,
+    value: Option<Expr>,
+    /// From `let` to the semicolon
+    context: BriseContext,
+}
+
+impl LetStmt {
+    pub fn new(name: Identifier, value: Option<Expr>, context: BriseContext) -> Self {
+        Self {
+            name,
+            value,
+            context,
+        }
+    }
+
+    pub fn name(&self) -> &Identifier {
+        &self.name
+    }
+
+    pub fn value(&self) -> Option<&Expr> {
+        self.value.as_ref()
+    }
+
+    pub fn context(&self) -> &BriseContext {
+        &self.context
+    }
+
+    pub fn span(&self) -> Span {
+        self.context.span()
+    }
+}
+
+impl From<LetStmt> for Stmt {
+    fn from(value: LetStmt) -> Self {
+        Self::new(StmtVariant::Let(value))
+    }
+}
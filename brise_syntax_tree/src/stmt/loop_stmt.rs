@@ -0,0 +1,35 @@
+use brise_token::{BriseContext, Span};
+
+use super::{block::Block, Stmt, StmtVariant};
+
+/// `loop { ... }`, only left with `break` or `return`
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct LoopStmt {
+    body: Block,
+    context: BriseContext,
+}
+
+impl LoopStmt {
+    pub fn new(body: Block, context: BriseContext) -> Self {
+        Self { body, context }
+    }
+
+    pub fn body(&self) -> &Block {
+        &self.body
+    }
+
+    pub fn context(&self) -> &BriseContext {
+        &self.context
+    }
+
+    pub fn span(&self) -> Span {
+        self.context.span()
+    }
+}
+
+impl From<LoopStmt> for Stmt {
+    fn from(value: LoopStmt) -> Self {
+        Self::new(StmtVariant::Loop(value))
+    }
+}
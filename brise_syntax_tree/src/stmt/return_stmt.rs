@@ -0,0 +1,38 @@
+use brise_token::{BriseContext, Span};
+
+use crate::expr::Expr;
+
+use super::{Stmt, StmtVariant};
+
+/// `return a;` or `return;`
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct ReturnStmt {
+    value: Option<Expr>,
+    /// Covers the semicolon
+    context: BriseContext,
+}
+
+impl ReturnStmt {
+    pub fn new(value: Option<Expr>, context: BriseContext) -> Self {
+        Self { value, context }
+    }
+
+    pub fn value(&self) -> Option<&Expr> {
+        self.value.as_ref()
+    }
+
+    pub fn context(&self) -> &BriseContext {
+        &self.context
+    }
+
+    pub fn span(&self) -> Span {
+        self.context.span()
+    }
+}
+
+impl From<ReturnStmt> for Stmt {
+    fn from(value: ReturnStmt) -> Self {
+        Self::new(StmtVariant::Return(value))
+    }
+}
@@ -0,0 +1,46 @@
+use brise_token::{BriseContext, Span};
+
+use crate::expr::Expr;
+
+use super::{block::Block, Stmt, StmtVariant};
+
+/// `while a < 10 { ... }`
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct WhileStmt {
+    condition: Expr,
+    body: Block,
+    context: BriseContext,
+}
+
+impl WhileStmt {
+    pub fn new(condition: Expr, body: Block, context: BriseContext) -> Self {
+        Self {
+            condition,
+            body,
+            context,
+        }
+    }
+
+    pub fn condition(&self) -> &Expr {
+        &self.condition
+    }
+
+    pub fn body(&self) -> &Block {
+        &self.body
+    }
+
+    pub fn context(&self) -> &BriseContext {
+        &self.context
+    }
+
+    pub fn span(&self) -> Span {
+        self.context.span()
+    }
+}
+
+impl From<WhileStmt> for Stmt {
+    fn from(value: WhileStmt) -> Self {
+        Self::new(StmtVariant::While(value))
+    }
+}
@@ -6,6 +6,13 @@ use std::{
     rc::Rc,
 };
 
+use crate::{FileId, Span};
+
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
 pub struct Line(NonZeroUsize);
 
@@ -15,6 +22,12 @@ impl Default for Line {
     }
 }
 
+impl Line {
+    pub fn get(&self) -> usize {
+        self.0.get()
+    }
+}
+
 impl From<NonZeroUsize> for Line {
     fn from(value: NonZeroUsize) -> Self {
         Self(value)
@@ -49,6 +62,11 @@ impl AddAssign<usize> for Line {
     }
 }
 
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
 pub struct Column(NonZeroUsize);
 
@@ -58,6 +76,12 @@ impl Default for Column {
     }
 }
 
+impl Column {
+    pub fn get(&self) -> usize {
+        self.0.get()
+    }
+}
+
 impl From<NonZeroUsize> for Column {
     fn from(value: NonZeroUsize) -> Self {
         Self(value)
@@ -107,37 +131,55 @@ impl BriseFile {
     }
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug, Default, PartialEq, Eq, Hash, Clone)]
 pub struct BriseContext {
+    /// Start of the span
     line: Line,
+    /// Start of the span
     col: Column,
-    file: Option<BriseFile>,
+    span: Span,
+    file: FileId,
 }
 
 impl Display for BriseContext {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        if let Some(file) = &self.file {
-            f.write_fmt(format_args!(
-                "[{}, {}:{}]",
-                file.0.display(),
-                self.line.0,
-                self.col.0
-            ))
-        } else {
-            f.write_fmt(format_args!("[{}:{}]", self.line.0, self.col.0))
-        }
+        f.write_fmt(format_args!("[{}:{}]", self.line.0, self.col.0))
     }
 }
 
 impl BriseContext {
-    pub fn new(file: Option<BriseFile>, line: impl Into<Line>, col: impl Into<Column>) -> Self {
+    pub fn new(
+        file: FileId,
+        span: impl Into<Span>,
+        line: impl Into<Line>,
+        col: impl Into<Column>,
+    ) -> Self {
         Self {
             file,
+            span: span.into(),
             line: line.into(),
             col: col.into(),
         }
     }
 
+    /// Context covering both `self` and `other`, the line and the column
+    /// are the ones of the context starting first
+    pub fn to(&self, other: &BriseContext) -> BriseContext {
+        let first = if other.span.start() < self.span.start() {
+            other
+        } else {
+            self
+        };
+
+        Self {
+            file: first.file,
+            span: self.span.to(other.span),
+            line: first.line,
+            col: first.col,
+        }
+    }
+
     pub fn line(&self) -> Line {
         self.line
     }
@@ -146,7 +188,11 @@ impl BriseContext {
         self.col
     }
 
-    pub fn file(&self) -> &Option<BriseFile> {
-        &self.file
+    pub fn span(&self) -> Span {
+        self.span
+    }
+
+    pub fn file(&self) -> FileId {
+        self.file
     }
 }
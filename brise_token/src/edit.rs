@@ -0,0 +1,32 @@
+use crate::Span;
+
+/// Replacement of a byte range of a source, like a keystroke in an editor
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub struct TextEdit {
+    span: Span,
+    replacement: String,
+}
+
+impl TextEdit {
+    pub fn new(span: impl Into<Span>, replacement: impl Into<String>) -> Self {
+        Self {
+            span: span.into(),
+            replacement: replacement.into(),
+        }
+    }
+
+    /// Range of the text before the edit which is replaced
+    pub fn span(&self) -> Span {
+        self.span
+    }
+
+    pub fn replacement(&self) -> &str {
+        &self.replacement
+    }
+
+    /// Number of bytes added to the text, negative when the edit removes text
+    pub fn delta(&self) -> isize {
+        self.replacement.len() as isize - self.span.len() as isize
+    }
+}
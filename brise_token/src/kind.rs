@@ -0,0 +1,382 @@
+use std::fmt::Display;
+
+use crate::TokenVariant;
+
+#[cfg(test)]
+mod tests;
+
+/// Variant of a token without its payload, see [`TokenVariant`]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum TokenKind {
+    LeftParen,
+    RightParen,
+    LeftBrace,
+    RightBrace,
+    LeftBracket,
+    RightBracket,
+    Comma,
+    Dot,
+    Minus,
+    Plus,
+    Semicolon,
+    Slash,
+    Star,
+    StarStar,
+    Percent,
+    PlusEqual,
+    MinusEqual,
+    StarEqual,
+    SlashEqual,
+    PercentEqual,
+    DotDot,
+    DotDotEqual,
+    FatArrow,
+    ColonColon,
+    BarGreater,
+    Bang,
+    BangEqual,
+    Equal,
+    EqualEqual,
+    Greater,
+    GreaterEqual,
+    Less,
+    LessEqual,
+    Identifier,
+    String,
+    FormattedString,
+    Integer,
+    Float,
+    AmpersandAmpersand,
+    BarBar,
+    BangRightChevron,
+    Return,
+    If,
+    Else,
+    While,
+    Loop,
+    For,
+    BriseSelf,
+    Let,
+    True,
+    False,
+    QuestionMark,
+    Colon,
+    Break,
+    Continue,
+    Fn,
+    RightArrow,
+    DocComment,
+    EndOfFile,
+    Error,
+}
+
+impl TokenKind {
+    /// Every kind, in the order of their declaration
+    pub const ALL: [TokenKind; 60] = [
+        Self::LeftParen,
+        Self::RightParen,
+        Self::LeftBrace,
+        Self::RightBrace,
+        Self::LeftBracket,
+        Self::RightBracket,
+        Self::Comma,
+        Self::Dot,
+        Self::Minus,
+        Self::Plus,
+        Self::Semicolon,
+        Self::Slash,
+        Self::Star,
+        Self::StarStar,
+        Self::Percent,
+        Self::PlusEqual,
+        Self::MinusEqual,
+        Self::StarEqual,
+        Self::SlashEqual,
+        Self::PercentEqual,
+        Self::DotDot,
+        Self::DotDotEqual,
+        Self::FatArrow,
+        Self::ColonColon,
+        Self::BarGreater,
+        Self::Bang,
+        Self::BangEqual,
+        Self::Equal,
+        Self::EqualEqual,
+        Self::Greater,
+        Self::GreaterEqual,
+        Self::Less,
+        Self::LessEqual,
+        Self::Identifier,
+        Self::String,
+        Self::FormattedString,
+        Self::Integer,
+        Self::Float,
+        Self::AmpersandAmpersand,
+        Self::BarBar,
+        Self::BangRightChevron,
+        Self::Return,
+        Self::If,
+        Self::Else,
+        Self::While,
+        Self::Loop,
+        Self::For,
+        Self::BriseSelf,
+        Self::Let,
+        Self::True,
+        Self::False,
+        Self::QuestionMark,
+        Self::Colon,
+        Self::Break,
+        Self::Continue,
+        Self::Fn,
+        Self::RightArrow,
+        Self::DocComment,
+        Self::EndOfFile,
+        Self::Error,
+    ];
+
+    /// Source text of the kind, `None` for kinds whose tokens carry a value
+    pub fn text(self) -> Option<&'static str> {
+        let text = match self {
+            Self::AmpersandAmpersand => "&&",
+            Self::Bang => "!",
+            Self::BangEqual => "!=",
+            Self::BangRightChevron => "!>",
+            Self::BarBar => "||",
+            Self::BarGreater => "|>",
+            Self::Break => "break",
+            Self::BriseSelf => "self",
+            Self::Colon => ":",
+            Self::ColonColon => "::",
+            Self::Comma => ",",
+            Self::Continue => "continue",
+            Self::Dot => ".",
+            Self::DotDot => "..",
+            Self::DotDotEqual => "..=",
+            Self::Else => "else",
+            Self::Equal => "=",
+            Self::EqualEqual => "==",
+            Self::False => "false",
+            Self::FatArrow => "=>",
+            Self::Fn => "fn",
+            Self::For => "for",
+            Self::Greater => ">",
+            Self::GreaterEqual => ">=",
+            Self::If => "if",
+            Self::LeftBrace => "{",
+            Self::LeftBracket => "[",
+            Self::LeftParen => "(",
+            Self::Less => "<",
+            Self::LessEqual => "<=",
+            Self::Let => "let",
+            Self::Loop => "loop",
+            Self::Minus => "-",
+            Self::MinusEqual => "-=",
+            Self::Percent => "%",
+            Self::PercentEqual => "%=",
+            Self::Plus => "+",
+            Self::PlusEqual => "+=",
+            Self::QuestionMark => "?",
+            Self::Return => "return",
+            Self::RightArrow => "->",
+            Self::RightBrace => "}",
+            Self::RightBracket => "]",
+            Self::RightParen => ")",
+            Self::Semicolon => ";",
+            Self::Slash => "/",
+            Self::SlashEqual => "/=",
+            Self::Star => "*",
+            Self::StarEqual => "*=",
+            Self::StarStar => "**",
+            Self::True => "true",
+            Self::While => "while",
+            Self::DocComment
+            | Self::EndOfFile
+            | Self::Error
+            | Self::Float
+            | Self::FormattedString
+            | Self::Identifier
+            | Self::Integer
+            | Self::String => return None,
+        };
+
+        Some(text)
+    }
+}
+
+impl From<&TokenVariant> for TokenKind {
+    fn from(value: &TokenVariant) -> Self {
+        match value {
+            TokenVariant::LeftParen => Self::LeftParen,
+            TokenVariant::RightParen => Self::RightParen,
+            TokenVariant::LeftBrace => Self::LeftBrace,
+            TokenVariant::RightBrace => Self::RightBrace,
+            TokenVariant::LeftBracket => Self::LeftBracket,
+            TokenVariant::RightBracket => Self::RightBracket,
+            TokenVariant::Comma => Self::Comma,
+            TokenVariant::Dot => Self::Dot,
+            TokenVariant::Minus => Self::Minus,
+            TokenVariant::Plus => Self::Plus,
+            TokenVariant::Semicolon => Self::Semicolon,
+            TokenVariant::Slash => Self::Slash,
+            TokenVariant::Star => Self::Star,
+            TokenVariant::StarStar => Self::StarStar,
+            TokenVariant::Percent => Self::Percent,
+            TokenVariant::PlusEqual => Self::PlusEqual,
+            TokenVariant::MinusEqual => Self::MinusEqual,
+            TokenVariant::StarEqual => Self::StarEqual,
+            TokenVariant::SlashEqual => Self::SlashEqual,
+            TokenVariant::PercentEqual => Self::PercentEqual,
+            TokenVariant::DotDot => Self::DotDot,
+            TokenVariant::DotDotEqual => Self::DotDotEqual,
+            TokenVariant::FatArrow => Self::FatArrow,
+            TokenVariant::ColonColon => Self::ColonColon,
+            TokenVariant::BarGreater => Self::BarGreater,
+            TokenVariant::Bang => Self::Bang,
+            TokenVariant::BangEqual => Self::BangEqual,
+            TokenVariant::Equal => Self::Equal,
+            TokenVariant::EqualEqual => Self::EqualEqual,
+            TokenVariant::Greater => Self::Greater,
+            TokenVariant::GreaterEqual => Self::GreaterEqual,
+            TokenVariant::Less => Self::Less,
+            TokenVariant::LessEqual => Self::LessEqual,
+            TokenVariant::Identifier(_) => Self::Identifier,
+            TokenVariant::String(_) => Self::String,
+            TokenVariant::FormattedString(_) => Self::FormattedString,
+            TokenVariant::Integer(_) => Self::Integer,
+            TokenVariant::Float(_) => Self::Float,
+            TokenVariant::AmpersandAmpersand => Self::AmpersandAmpersand,
+            TokenVariant::BarBar => Self::BarBar,
+            TokenVariant::BangRightChevron => Self::BangRightChevron,
+            TokenVariant::Return => Self::Return,
+            TokenVariant::If => Self::If,
+            TokenVariant::Else => Self::Else,
+            TokenVariant::While => Self::While,
+            TokenVariant::Loop => Self::Loop,
+            TokenVariant::For => Self::For,
+            TokenVariant::BriseSelf => Self::BriseSelf,
+            TokenVariant::Let => Self::Let,
+            TokenVariant::True => Self::True,
+            TokenVariant::False => Self::False,
+            TokenVariant::QuestionMark => Self::QuestionMark,
+            TokenVariant::Colon => Self::Colon,
+            TokenVariant::Break => Self::Break,
+            TokenVariant::Continue => Self::Continue,
+            TokenVariant::Fn => Self::Fn,
+            TokenVariant::RightArrow => Self::RightArrow,
+            TokenVariant::DocComment(_) => Self::DocComment,
+            TokenVariant::EndOfFile => Self::EndOfFile,
+            TokenVariant::Error => Self::Error,
+        }
+    }
+}
+
+impl Display for TokenKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(text) = self.text() {
+            return write!(f, "`{text}`");
+        }
+
+        let description = match self {
+            Self::DocComment => "a doc comment",
+            Self::EndOfFile => "the end of the file",
+            Self::Error => "invalid input",
+            Self::Float => "a float",
+            Self::FormattedString => "a formatted string",
+            Self::Identifier => "an identifier",
+            Self::Integer => "an integer",
+            Self::String => "a string",
+            _ => unreachable!(),
+        };
+
+        f.write_str(description)
+    }
+}
+
+/// Set of token kinds, one bit per kind
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Default, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct TokenSet(u64);
+
+const _: () = assert!(TokenKind::ALL.len() <= u64::BITS as usize);
+
+impl TokenSet {
+    pub const EMPTY: Self = Self(0);
+
+    pub const fn new(kinds: &[TokenKind]) -> Self {
+        let mut set = Self::EMPTY;
+        let mut index = 0;
+
+        while index < kinds.len() {
+            set = set.with(kinds[index]);
+            index += 1;
+        }
+
+        set
+    }
+
+    pub const fn with(self, kind: TokenKind) -> Self {
+        Self(self.0 | Self::bit(kind))
+    }
+
+    pub const fn union(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+
+    /// Kinds of the set which aren't in `other`
+    pub const fn difference(self, other: Self) -> Self {
+        Self(self.0 & !other.0)
+    }
+
+    pub fn insert(&mut self, kind: TokenKind) {
+        *self = self.with(kind);
+    }
+
+    pub const fn contains(self, kind: TokenKind) -> bool {
+        self.0 & Self::bit(kind) != 0
+    }
+
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    pub const fn len(self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    /// Kinds of the set, in the order of [`TokenKind::ALL`]
+    pub fn iter(self) -> impl Iterator<Item = TokenKind> {
+        TokenKind::ALL
+            .into_iter()
+            .filter(move |kind| self.contains(*kind))
+    }
+
+    const fn bit(kind: TokenKind) -> u64 {
+        1 << kind as u64
+    }
+}
+
+impl From<TokenKind> for TokenSet {
+    fn from(value: TokenKind) -> Self {
+        Self::EMPTY.with(value)
+    }
+}
+
+impl FromIterator<TokenKind> for TokenSet {
+    fn from_iter<T: IntoIterator<Item = TokenKind>>(iter: T) -> Self {
+        iter.into_iter().fold(Self::EMPTY, Self::with)
+    }
+}
+
+/// `` `;` `` for a single kind, `` one of `)`, `,` `` for several
+impl Display for TokenSet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.len() > 1 {
+            f.write_str("one of ")?;
+        }
+        let kinds: Vec<String> = self.iter().map(|kind| kind.to_string()).collect();
+
+        f.write_str(&kinds.join(", "))
+    }
+}
@@ -0,0 +1,64 @@
+use super::*;
+
+#[test]
+fn kinds_match_their_declaration_order() {
+    for (index, kind) in TokenKind::ALL.into_iter().enumerate() {
+        assert_eq!(index, kind as usize);
+    }
+}
+
+#[test]
+fn kind_of_variants() {
+    assert_eq!(TokenKind::RightParen, TokenVariant::RightParen.kind());
+    assert_eq!(
+        TokenKind::Identifier,
+        TokenVariant::Identifier("a".into()).kind()
+    );
+    assert_eq!(TokenKind::Float, TokenVariant::Float(1.5).kind());
+    assert_eq!(
+        TokenKind::FormattedString,
+        TokenVariant::FormattedString(vec![]).kind()
+    );
+}
+
+#[test]
+fn fixed_kinds_display_their_text() {
+    assert_eq!(Some("..="), TokenKind::DotDotEqual.text());
+    assert_eq!("`;`", TokenKind::Semicolon.to_string());
+    assert_eq!(None, TokenKind::Integer.text());
+    assert_eq!("an identifier", TokenKind::Identifier.to_string());
+}
+
+#[test]
+fn set_operations() {
+    let mut set = TokenSet::new(&[TokenKind::Comma, TokenKind::RightParen]);
+
+    assert_eq!(2, set.len());
+    assert!(set.contains(TokenKind::Comma));
+    assert!(!set.contains(TokenKind::Semicolon));
+
+    set.insert(TokenKind::Error);
+    assert!(set.contains(TokenKind::Error));
+    assert_eq!(
+        set,
+        TokenSet::from(TokenKind::Error)
+            .union(TokenSet::new(&[TokenKind::RightParen, TokenKind::Comma]))
+    );
+    assert!(TokenSet::EMPTY.is_empty());
+}
+
+#[test]
+fn every_kind_fits_in_a_set() {
+    let set: TokenSet = TokenKind::ALL.into_iter().collect();
+
+    assert_eq!(TokenKind::ALL.len(), set.len());
+    assert!(TokenKind::ALL.into_iter().eq(set.iter()));
+}
+
+#[test]
+fn set_display() {
+    let set = TokenSet::new(&[TokenKind::Comma, TokenKind::RightParen]);
+
+    assert_eq!("one of `)`, `,`", set.to_string());
+    assert_eq!("`;`", TokenSet::from(TokenKind::Semicolon).to_string());
+}
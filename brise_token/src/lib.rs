@@ -1,11 +1,28 @@
 mod context;
+mod edit;
+mod kind;
+mod line_index;
 mod raw_string;
+#[cfg(feature = "serde")]
+pub mod serialization;
+mod source_map;
+mod span;
+mod symbol;
+mod trivia;
 
 use std::{fmt::Display, hash::Hash};
 
 pub use context::{BriseContext, BriseFile, Column, Line};
+pub use edit::TextEdit;
+pub use kind::{TokenKind, TokenSet};
+pub use line_index::{ColumnUnit, LineIndex};
 pub use raw_string::RawString;
+pub use source_map::{FileId, SourceFile, SourceMap, SourceName};
+pub use span::Span;
+pub use symbol::Symbol;
+pub use trivia::{Trivia, TriviaKind};
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug, PartialEq)]
 pub enum TokenVariant {
     /// `(`
@@ -34,6 +51,30 @@ pub enum TokenVariant {
     Slash,
     /// `*`
     Star,
+    /// `**`
+    StarStar,
+    /// `%`
+    Percent,
+    /// `+=`
+    PlusEqual,
+    /// `-=`
+    MinusEqual,
+    /// `*=`
+    StarEqual,
+    /// `/=`
+    SlashEqual,
+    /// `%=`
+    PercentEqual,
+    /// `..`
+    DotDot,
+    /// `..=`
+    DotDotEqual,
+    /// `=>`
+    FatArrow,
+    /// `::`
+    ColonColon,
+    /// `|>`
+    BarGreater,
     /// `!`
     Bang,
     /// `!=`
@@ -51,11 +92,14 @@ pub enum TokenVariant {
     /// `<=`
     LessEqual,
     Identifier(RawString),
-    /// ""
+    /// `""`, `r#""#` or `"""` spanning several lines
     String(RawString),
     /// ``` $"`{}`" ```
     FormattedString(Vec<(RawString, Vec<Token>)>),
-    Number(f64),
+    /// `42`, `0xFF`, `0o17`, `0b1010`, `1_000`
+    Integer(i64),
+    /// `4.2`, `1e-9`
+    Float(#[cfg_attr(feature = "serde", serde(with = "crate::serialization::float"))] f64),
     /// `&&`
     AmpersandAmpersand,
     /// `||`
@@ -94,9 +138,19 @@ pub enum TokenVariant {
     Fn,
     /// `->`
     RightArrow,
+    /// `/// documentation`
+    DocComment(RawString),
+    /// Only produced by the lossless lexing mode, to hold the trivia ending the input
+    EndOfFile,
+    /// Invalid input, the lexer reports why along with the tokens
+    Error,
 }
 
 impl TokenVariant {
+    pub fn kind(&self) -> TokenKind {
+        self.into()
+    }
+
     pub fn is_equality(&self) -> bool {
         matches!(self, Self::BangEqual | Self::EqualEqual)
     }
@@ -113,7 +167,23 @@ impl TokenVariant {
     }
 
     pub fn is_factor(&self) -> bool {
-        matches!(self, Self::Star | Self::Slash)
+        matches!(self, Self::Star | Self::Slash | Self::Percent)
+    }
+
+    pub fn is_assignment(&self) -> bool {
+        matches!(
+            self,
+            Self::Equal
+                | Self::PlusEqual
+                | Self::MinusEqual
+                | Self::StarEqual
+                | Self::SlashEqual
+                | Self::PercentEqual
+        )
+    }
+
+    pub fn is_range(&self) -> bool {
+        matches!(self, Self::DotDot | Self::DotDotEqual)
     }
 
     pub fn is_unary(&self) -> bool {
@@ -123,7 +193,8 @@ impl TokenVariant {
     pub fn is_literal(&self) -> bool {
         matches!(
             self,
-            Self::Number(_)
+            Self::Integer(_)
+                | Self::Float(_)
                 | Self::String(_)
                 | Self::FormattedString(_)
                 | Self::False
@@ -141,16 +212,24 @@ impl Display for TokenVariant {
             Self::BangEqual => "!=",
             Self::BangRightChevron => "!>",
             Self::BarBar => "||",
+            Self::BarGreater => "|>",
             Self::Break => "break",
             Self::BriseSelf => "self",
             Self::Colon => ":",
+            Self::ColonColon => "::",
             Self::Comma => ",",
+            Self::DocComment(text) => &format!("///{text}"),
             Self::Continue => "continue",
             Self::Dot => ".",
+            Self::DotDot => "..",
+            Self::DotDotEqual => "..=",
             Self::Else => "else",
+            Self::EndOfFile => "<end of file>",
+            Self::Error => "<error>",
             Self::Equal => "=",
             Self::EqualEqual => "==",
             Self::False => "false",
+            Self::FatArrow => "=>",
             Self::Fn => "fn",
             Self::For => "for",
             Self::FormattedString(values) => &format!(
@@ -180,8 +259,13 @@ impl Display for TokenVariant {
             Self::Let => "let",
             Self::Loop => "loop",
             Self::Minus => "-",
-            Self::Number(num) => &num.to_string(),
+            Self::MinusEqual => "-=",
+            Self::Integer(num) => &num.to_string(),
+            Self::Float(num) => &num.to_string(),
+            Self::Percent => "%",
+            Self::PercentEqual => "%=",
             Self::Plus => "+",
+            Self::PlusEqual => "+=",
             Self::QuestionMark => "?",
             Self::Return => "return",
             Self::RightArrow => "->",
@@ -190,7 +274,10 @@ impl Display for TokenVariant {
             Self::RightParen => ")",
             Self::Semicolon => ";",
             Self::Slash => "/",
+            Self::SlashEqual => "/=",
             Self::Star => "*",
+            Self::StarEqual => "*=",
+            Self::StarStar => "**",
             Self::String(string) => &format!("\"{string}\""),
             Self::True => "true",
             Self::While => "while",
@@ -200,10 +287,23 @@ impl Display for TokenVariant {
     }
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug, PartialEq)]
 pub struct Token {
     variant: TokenVariant,
     context: BriseContext,
+    /// Trivia before the token, starting after the trailing trivia of the previous token
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    leading_trivia: Vec<Trivia>,
+    /// Trivia after the token, up to the end of its line
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    trailing_trivia: Vec<Trivia>,
 }
 
 impl Hash for Token {
@@ -214,7 +314,20 @@ impl Hash for Token {
 
 impl Token {
     pub fn new(variant: TokenVariant, context: BriseContext) -> Self {
-        Self { variant, context }
+        Self {
+            variant,
+            context,
+            leading_trivia: vec![],
+            trailing_trivia: vec![],
+        }
+    }
+
+    pub fn with_trivia(self, leading_trivia: Vec<Trivia>, trailing_trivia: Vec<Trivia>) -> Self {
+        Self {
+            leading_trivia,
+            trailing_trivia,
+            ..self
+        }
     }
 
     pub fn variant(&self) -> &TokenVariant {
@@ -225,6 +338,85 @@ impl Token {
         &self.context
     }
 
+    pub fn kind(&self) -> TokenKind {
+        self.variant.kind()
+    }
+
+    /// Compares the variants, nested tokens included, without looking at
+    /// the contexts and the trivia
+    pub fn same_variant(&self, other: &Token) -> bool {
+        match (&self.variant, &other.variant) {
+            (TokenVariant::FormattedString(parts), TokenVariant::FormattedString(other_parts)) => {
+                parts.len() == other_parts.len()
+                    && parts.iter().zip(other_parts).all(
+                        |((text, tokens), (other_text, other_tokens))| {
+                            text == other_text
+                                && tokens.len() == other_tokens.len()
+                                && tokens
+                                    .iter()
+                                    .zip(other_tokens)
+                                    .all(|(token, other)| token.same_variant(other))
+                        },
+                    )
+            }
+            (variant, other_variant) => variant == other_variant,
+        }
+    }
+
+    pub fn span(&self) -> Span {
+        self.context.span()
+    }
+
+    pub fn leading_trivia(&self) -> &[Trivia] {
+        &self.leading_trivia
+    }
+
+    pub fn trailing_trivia(&self) -> &[Trivia] {
+        &self.trailing_trivia
+    }
+
+    /// Span of the token along with its trivia
+    pub fn full_span(&self) -> Span {
+        let span = self.span();
+        let start = self
+            .leading_trivia
+            .first()
+            .map_or(span, |trivia| trivia.span());
+        let end = self
+            .trailing_trivia
+            .last()
+            .map_or(span, |trivia| trivia.span());
+
+        start.to(end)
+    }
+
+    /// Moves the token along with its trivia and its nested tokens, `relocate`
+    /// gives the new context of every context of the token
+    pub fn relocate(&mut self, relocate: &impl Fn(&BriseContext) -> BriseContext) {
+        let context = relocate(&self.context);
+        let delta = context.span().start() as isize - self.context.span().start() as isize;
+
+        for trivia in self
+            .leading_trivia
+            .iter_mut()
+            .chain(self.trailing_trivia.iter_mut())
+        {
+            *trivia = Trivia::new(trivia.kind(), trivia.span().shifted(delta));
+        }
+
+        if let TokenVariant::FormattedString(parts) = &mut self.variant {
+            for token in parts.iter_mut().flat_map(|(_, tokens)| tokens) {
+                token.relocate(relocate);
+            }
+        }
+
+        self.context = context;
+    }
+
+    pub fn into_parts(self) -> (TokenVariant, BriseContext) {
+        (self.variant, self.context)
+    }
+
     pub fn is_equality(&self) -> bool {
         self.variant.is_equality()
     }
@@ -241,6 +433,14 @@ impl Token {
         self.variant.is_factor()
     }
 
+    pub fn is_assignment(&self) -> bool {
+        self.variant.is_assignment()
+    }
+
+    pub fn is_range(&self) -> bool {
+        self.variant.is_range()
+    }
+
     pub fn is_unary(&self) -> bool {
         self.variant.is_unary()
     }
@@ -0,0 +1,192 @@
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+use crate::{Column, Line, Span, TextEdit};
+
+const BYTE_ORDER_MARK: char = '\u{feff}';
+
+#[cfg(test)]
+mod tests;
+
+/// Unit in which the columns of a line are counted
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum ColumnUnit {
+    /// Bytes of the UTF-8 text
+    Byte,
+    /// Unicode scalar values, the unit of the columns of a [`crate::BriseContext`]
+    Char,
+    /// UTF-16 code units, the unit of editor protocols
+    Utf16,
+    /// Terminal cells, a grapheme like `😀` or `字` takes two cells
+    DisplayWidth,
+}
+
+/// Converts byte offsets of a text to lines and columns, and back. The text itself
+/// is not stored, every conversion takes the text the index was built from.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct LineIndex {
+    /// Byte offset of the start of each line
+    line_starts: Vec<usize>,
+    /// On ASCII lines, every unit but the display width is a byte
+    ascii_lines: Vec<bool>,
+}
+
+impl LineIndex {
+    pub fn new(text: &str) -> Self {
+        let line_starts: Vec<usize> = std::iter::once(0)
+            .chain(text.match_indices('\n').map(|(offset, _)| offset + 1))
+            .collect();
+        let mut index = Self {
+            line_starts,
+            ascii_lines: vec![],
+        };
+        index.ascii_lines = (0..index.line_count())
+            .map(|line_index| index.is_ascii_line(text, line_index))
+            .collect();
+
+        index
+    }
+
+    pub fn line_count(&self) -> usize {
+        self.line_starts.len()
+    }
+
+    /// Line holding a byte offset, offsets past the end are on the last line
+    pub fn line(&self, offset: usize) -> Line {
+        Line::default() + self.line_index(offset)
+    }
+
+    /// Byte range of a line, without its line ending, `\n` or `\r\n`, and without
+    /// the byte order mark starting the text
+    ///
+    /// # Panics
+    ///
+    /// Panics if the line is not in the text
+    pub fn line_span(&self, text: &str, line: Line) -> Span {
+        let line_index = line.get() - 1;
+        let start = if line_index == 0 && text.starts_with(BYTE_ORDER_MARK) {
+            BYTE_ORDER_MARK.len_utf8()
+        } else {
+            self.line_starts[line_index]
+        };
+        let end = match self.line_starts.get(line_index + 1) {
+            Some(next_start) if text[..next_start - 1].ends_with('\r') => next_start - 2,
+            Some(next_start) => next_start - 1,
+            None => text.len(),
+        };
+
+        Span::new(start, end)
+    }
+
+    /// Line and column of a byte offset, the line ending and the byte order mark
+    /// take no column
+    pub fn location(&self, text: &str, offset: usize, unit: ColumnUnit) -> (Line, Column) {
+        let offset = offset.min(text.len());
+        let line_index = self.line_index(offset);
+        let line_span = self.line_span(text, Line::default() + line_index);
+        let prefix = &text[line_span.start()..offset.clamp(line_span.start(), line_span.end())];
+
+        let len = if self.ascii_lines[line_index] && unit != ColumnUnit::DisplayWidth {
+            prefix.len()
+        } else {
+            Self::measure(prefix, unit)
+        };
+
+        (Line::default() + line_index, Column::default() + len)
+    }
+
+    /// Byte offset of a line and a column. A column inside a char or a grapheme
+    /// gives the start of that char or grapheme, a column past the end of the line
+    /// gives the end of the line.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the line is not in the text
+    pub fn offset(&self, text: &str, line: Line, column: Column, unit: ColumnUnit) -> usize {
+        let line_span = self.line_span(text, line);
+        let line_text = &text[line_span.start()..line_span.end()];
+        let target = column.get() - 1;
+
+        let offset = match unit {
+            ColumnUnit::DisplayWidth => {
+                Self::offset_in_line(line_text.graphemes(true), target, unit)
+            }
+            _ => Self::offset_in_line(line_text.split_inclusive(|_: char| true), target, unit),
+        };
+
+        line_span.start() + offset
+    }
+
+    /// Keeps the index up to date with the edited text, only the lines around
+    /// the edit are scanned
+    pub fn edit(&mut self, text: &str, edit: &TextEdit) {
+        let span = edit.span();
+        let first_removed = self
+            .line_starts
+            .partition_point(|start| *start <= span.start());
+        let first_kept = self
+            .line_starts
+            .partition_point(|start| *start <= span.end());
+        let inserted: Vec<usize> = edit
+            .replacement()
+            .match_indices('\n')
+            .map(|(offset, _)| span.start() + offset + 1)
+            .collect();
+        let inserted_count = inserted.len();
+
+        self.line_starts.splice(first_removed..first_kept, inserted);
+        for start in &mut self.line_starts[first_removed + inserted_count..] {
+            *start = start.saturating_add_signed(edit.delta());
+        }
+
+        // The line holding the start of the edit and the inserted lines are scanned again
+        let ascii_lines: Vec<bool> = (first_removed - 1..first_removed + inserted_count)
+            .map(|line_index| self.is_ascii_line(text, line_index))
+            .collect();
+        self.ascii_lines
+            .splice(first_removed - 1..first_kept, ascii_lines);
+    }
+
+    fn line_index(&self, offset: usize) -> usize {
+        self.line_starts.partition_point(|start| *start <= offset) - 1
+    }
+
+    fn is_ascii_line(&self, text: &str, line_index: usize) -> bool {
+        let end = self
+            .line_starts
+            .get(line_index + 1)
+            .copied()
+            .unwrap_or(text.len());
+
+        text[self.line_starts[line_index]..end].is_ascii()
+    }
+
+    /// Byte length of the pieces, chars or graphemes, fitting before the target column
+    fn offset_in_line<'a>(
+        pieces: impl Iterator<Item = &'a str>,
+        target: usize,
+        unit: ColumnUnit,
+    ) -> usize {
+        let mut len = 0;
+        let mut offset = 0;
+
+        for piece in pieces {
+            len += Self::measure(piece, unit);
+            if len > target {
+                break;
+            }
+            offset += piece.len();
+        }
+
+        offset
+    }
+
+    fn measure(text: &str, unit: ColumnUnit) -> usize {
+        match unit {
+            ColumnUnit::Byte => text.len(),
+            ColumnUnit::Char => text.chars().count(),
+            ColumnUnit::Utf16 => text.chars().map(char::len_utf16).sum(),
+            ColumnUnit::DisplayWidth => text.width(),
+        }
+    }
+}
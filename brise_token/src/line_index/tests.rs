@@ -0,0 +1,104 @@
+use std::num::NonZeroUsize;
+
+use super::*;
+
+fn line(line: usize) -> Line {
+    Line::from(NonZeroUsize::new(line).unwrap())
+}
+
+fn column(col: usize) -> Column {
+    Column::from(NonZeroUsize::new(col).unwrap())
+}
+
+#[test]
+fn columns_in_every_unit() {
+    // `é` is 2 bytes, `😀` is 4 bytes, 2 UTF-16 code units and 2 cells wide
+    let text = "ab\né😀x\n字";
+    let index = LineIndex::new(text);
+    let x = text.find('x').unwrap();
+
+    let columns: Vec<usize> = [
+        ColumnUnit::Byte,
+        ColumnUnit::Char,
+        ColumnUnit::Utf16,
+        ColumnUnit::DisplayWidth,
+    ]
+    .into_iter()
+    .map(|unit| {
+        let (x_line, x_col) = index.location(text, x, unit);
+        assert_eq!(line(2), x_line);
+        x_col.get()
+    })
+    .collect();
+
+    assert_eq!(vec![7, 3, 4, 4], columns);
+    assert_eq!(
+        (line(3), column(1)),
+        index.location(text, text.len() - 3, ColumnUnit::DisplayWidth)
+    );
+    assert_eq!(
+        (line(3), column(3)),
+        index.location(text, text.len(), ColumnUnit::DisplayWidth)
+    );
+}
+
+#[test]
+fn offsets_from_columns() {
+    let text = "ab\né😀x\n字";
+    let index = LineIndex::new(text);
+    let x = text.find('x').unwrap();
+
+    for unit in [
+        ColumnUnit::Byte,
+        ColumnUnit::Char,
+        ColumnUnit::Utf16,
+        ColumnUnit::DisplayWidth,
+    ] {
+        let (x_line, x_col) = index.location(text, x, unit);
+        assert_eq!(x, index.offset(text, x_line, x_col, unit), "{unit:?}");
+    }
+
+    // Inside the surrogate pair of `😀`
+    assert_eq!(5, index.offset(text, line(2), column(3), ColumnUnit::Utf16));
+    // Past the end of the line
+    assert_eq!(2, index.offset(text, line(1), column(10), ColumnUnit::Char));
+}
+
+#[test]
+fn combined_graphemes_are_one_cell() {
+    // `e` followed by a combining acute accent
+    let text = "e\u{301}x";
+    let index = LineIndex::new(text);
+
+    assert_eq!(column(3), index.location(text, 3, ColumnUnit::Char).1);
+    assert_eq!(
+        column(2),
+        index.location(text, 3, ColumnUnit::DisplayWidth).1
+    );
+    assert_eq!(
+        3,
+        index.offset(text, line(1), column(2), ColumnUnit::DisplayWidth)
+    );
+}
+
+#[test]
+fn line_endings_and_byte_order_mark_take_no_column() {
+    let text = "\u{feff}ab\r\ncd\r\n";
+    let index = LineIndex::new(text);
+
+    assert_eq!(Span::new(3, 5), index.line_span(text, line(1)));
+    assert_eq!(Span::new(7, 9), index.line_span(text, line(2)));
+    assert_eq!(
+        (line(1), column(1)),
+        index.location(text, 3, ColumnUnit::Char)
+    );
+    assert_eq!(
+        (line(1), column(3)),
+        index.location(text, 6, ColumnUnit::Char)
+    );
+    assert_eq!(
+        (line(2), column(3)),
+        index.location(text, 10, ColumnUnit::Byte)
+    );
+    assert_eq!(3, index.offset(text, line(1), column(1), ColumnUnit::Utf16));
+}
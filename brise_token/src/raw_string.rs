@@ -1,7 +1,17 @@
-use std::{fmt::Display, rc::Rc};
+use std::fmt::{Debug, Display};
 
-#[derive(Debug, PartialEq, Eq, Hash, Clone)]
-pub struct RawString(Rc<str>);
+use crate::Symbol;
+
+/// Identifier or string text, interned so that copies are free and comparisons
+/// don't read the text
+#[derive(PartialEq, Eq, Hash, Clone, Copy)]
+pub struct RawString(Symbol);
+
+impl Debug for RawString {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("RawString").field(&self.as_str()).finish()
+    }
+}
 
 impl Display for RawString {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -9,18 +19,28 @@ impl Display for RawString {
     }
 }
 
-impl<T: Into<Rc<str>>> From<T> for RawString {
+impl<T: AsRef<str>> From<T> for RawString {
     fn from(value: T) -> Self {
-        Self(value.into())
+        Self(Symbol::intern(value.as_ref()))
+    }
+}
+
+impl From<Symbol> for RawString {
+    fn from(value: Symbol) -> Self {
+        Self(value)
     }
 }
 
 impl RawString {
-    pub fn new(raw_string: Rc<str>) -> Self {
-        Self(raw_string)
+    pub fn new(symbol: Symbol) -> Self {
+        Self(symbol)
+    }
+
+    pub fn symbol(&self) -> Symbol {
+        self.0
     }
 
-    pub fn as_str(&self) -> &str {
-        &self.0
+    pub fn as_str(&self) -> &'static str {
+        self.0.as_str()
     }
 }
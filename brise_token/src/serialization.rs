@@ -0,0 +1,84 @@
+//! JSON shape of the tokens, with the `serde` feature enabled:
+//!
+//! - `Span`: `{"start": 4, "end": 7}`, in bytes
+//! - `BriseContext`: `{"line": 1, "col": 5, "span": {...}, "file": 0}`, where `file`
+//!   is the index of the source in its `SourceMap`
+//! - `RawString` and `Symbol`: the text itself, `"name"`, as symbol ids change
+//!   from one run to another
+//! - `TokenVariant`: the name of the variant when it holds no data, `"LeftParen"`,
+//!   an object keyed by the name otherwise, `{"Identifier": "a"}`, `{"Float": 1.5}`,
+//!   `{"FormattedString": [["text ", [...tokens]]]}`. A float out of the range of JSON
+//!   numbers is a string, `{"Float": "inf"}`, see [`float`]
+//! - `Token`: `{"variant": ..., "context": ..., "leading_trivia": [...], "trailing_trivia": [...]}`,
+//!   the trivia lists are left out when empty
+//! - `Trivia`: `{"kind": "Whitespace", "span": {...}}`
+//! - `TextEdit`: `{"span": {...}, "replacement": "text"}`
+//!
+//! Renaming a variant or a field changes the shape, external tools depend on it.
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{RawString, Symbol};
+
+impl Serialize for Symbol {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for Symbol {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let text = String::deserialize(deserializer)?;
+
+        Ok(Symbol::intern(&text))
+    }
+}
+
+impl Serialize for RawString {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.symbol().serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for RawString {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Symbol::deserialize(deserializer).map(RawString::from)
+    }
+}
+
+/// `f64` fields written as a number, or as `"inf"`, `"-inf"` or `"NaN"` which JSON
+/// numbers can't hold. Used with `#[serde(with = "brise_token::serialization::float")]`
+pub mod float {
+    use serde::{de::Unexpected, Deserialize, Deserializer, Serializer};
+
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Float {
+        Number(f64),
+        Text(String),
+    }
+
+    pub fn serialize<S: Serializer>(value: &f64, serializer: S) -> Result<S::Ok, S::Error> {
+        match *value {
+            value if value.is_finite() => serializer.serialize_f64(value),
+            f64::INFINITY => serializer.serialize_str("inf"),
+            f64::NEG_INFINITY => serializer.serialize_str("-inf"),
+            _ => serializer.serialize_str("NaN"),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<f64, D::Error> {
+        match Float::deserialize(deserializer)? {
+            Float::Number(value) => Ok(value),
+            Float::Text(text) => match text.as_str() {
+                "inf" => Ok(f64::INFINITY),
+                "-inf" => Ok(f64::NEG_INFINITY),
+                "NaN" => Ok(f64::NAN),
+                _ => Err(serde::de::Error::invalid_value(
+                    Unexpected::Str(&text),
+                    &"a number, \"inf\", \"-inf\" or \"NaN\"",
+                )),
+            },
+        }
+    }
+}
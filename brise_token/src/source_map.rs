@@ -0,0 +1,204 @@
+use std::fmt::Display;
+
+use crate::{BriseContext, BriseFile, Column, ColumnUnit, Line, LineIndex, Span, TextEdit};
+
+#[cfg(test)]
+mod tests;
+
+/// Index of a source stored in a [`SourceMap`]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+#[derive(Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+pub struct FileId(u32);
+
+impl FileId {
+    pub fn index(&self) -> usize {
+        self.0 as usize
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone)]
+pub enum SourceName {
+    File(BriseFile),
+    /// In-memory source, like a REPL line
+    Snippet,
+}
+
+impl Display for SourceName {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::File(file) => f.write_fmt(format_args!("{}", file.as_path().display())),
+            Self::Snippet => f.write_str("<snippet>"),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct SourceFile {
+    name: SourceName,
+    text: String,
+    line_index: LineIndex,
+}
+
+impl SourceFile {
+    fn new(name: SourceName, text: String) -> Self {
+        Self {
+            name,
+            line_index: LineIndex::new(&text),
+            text,
+        }
+    }
+
+    pub fn name(&self) -> &SourceName {
+        &self.name
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn line_index(&self) -> &LineIndex {
+        &self.line_index
+    }
+
+    pub fn line_count(&self) -> usize {
+        self.line_index.line_count()
+    }
+
+    /// Line and column of a byte offset, the column is counted in chars
+    pub fn location(&self, offset: usize) -> (Line, Column) {
+        self.location_in(offset, ColumnUnit::Char)
+    }
+
+    pub fn location_in(&self, offset: usize, unit: ColumnUnit) -> (Line, Column) {
+        self.line_index.location(&self.text, offset, unit)
+    }
+
+    /// Byte range of a line, without its line ending
+    pub fn line_span(&self, line: Line) -> Span {
+        self.line_index.line_span(&self.text, line)
+    }
+
+    pub fn line_text(&self, line: Line) -> &str {
+        self.snippet(self.line_span(line))
+    }
+
+    pub fn snippet(&self, span: Span) -> &str {
+        &self.text[span.start()..span.end()]
+    }
+
+    fn edit(&mut self, edit: &TextEdit) {
+        let span = edit.span();
+        self.text
+            .replace_range(span.start()..span.end(), edit.replacement());
+        self.line_index.edit(&self.text, edit);
+    }
+}
+
+/// Owns the text of every source lexed, files as well as in-memory snippets
+#[derive(Debug, Default)]
+pub struct SourceMap {
+    files: Vec<SourceFile>,
+}
+
+impl SourceMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_file(&mut self, file: impl Into<BriseFile>, text: String) -> FileId {
+        self.add(SourceName::File(file.into()), text)
+    }
+
+    pub fn load_file(&mut self, file: impl Into<BriseFile>) -> Result<FileId, std::io::Error> {
+        let file = file.into();
+        let text = std::fs::read_to_string(file.as_path())?;
+
+        Ok(self.add_file(file, text))
+    }
+
+    pub fn add_snippet(&mut self, text: String) -> FileId {
+        self.add(SourceName::Snippet, text)
+    }
+
+    fn add(&mut self, name: SourceName, text: String) -> FileId {
+        let id = FileId(
+            self.files
+                .len()
+                .try_into()
+                .expect("too many sources in the source map"),
+        );
+        self.files.push(SourceFile::new(name, text));
+
+        id
+    }
+
+    /// # Panics
+    ///
+    /// Panics if the id comes from another source map
+    pub fn get(&self, file: FileId) -> &SourceFile {
+        &self.files[file.index()]
+    }
+
+    /// # Panics
+    ///
+    /// Panics if the span of the edit is not inside the source or does not
+    /// fall on char boundaries
+    pub fn edit(&mut self, file: FileId, edit: &TextEdit) {
+        self.files[file.index()].edit(edit)
+    }
+
+    pub fn text(&self, file: FileId) -> &str {
+        self.get(file).text()
+    }
+
+    pub fn location(&self, file: FileId, offset: usize) -> (Line, Column) {
+        self.get(file).location(offset)
+    }
+
+    /// Line and column of a byte offset, the column is counted in `unit`
+    pub fn location_in(&self, file: FileId, offset: usize, unit: ColumnUnit) -> (Line, Column) {
+        self.get(file).location_in(offset, unit)
+    }
+
+    pub fn snippet(&self, file: FileId, span: Span) -> &str {
+        self.get(file).snippet(span)
+    }
+
+    /// Renders a message along with the source line pointed by the context:
+    ///
+    /// ```text
+    /// main.brs:1:9: Unexpected character: @
+    ///   |
+    /// 1 | let a = @;
+    ///   |         ^
+    /// ```
+    pub fn render(&self, context: &BriseContext, message: impl Display) -> String {
+        let file = self.get(context.file());
+        let span = context.span();
+        let (line, col) = file.location(span.start());
+        let line_span = file.line_span(line);
+        // A span can start on a line ending left out of the line, like the `\n` of `\r\n`
+        let start = span.start().clamp(line_span.start(), line_span.end());
+        let underline_end = span.end().clamp(start, line_span.end());
+        // Terminals draw some graphemes on two cells
+        let (_, underline_start) = file.location_in(start, ColumnUnit::DisplayWidth);
+        let (_, underline_stop) = file.location_in(underline_end, ColumnUnit::DisplayWidth);
+        let underline_len = (underline_stop.get() - underline_start.get()).max(1);
+
+        let line_number = line.get().to_string();
+        let gutter = " ".repeat(line_number.len());
+
+        format!(
+            "{}:{line_number}:{}: {message}\n{gutter} |\n{line_number} | {}\n{gutter} | {}{}",
+            file.name(),
+            col.get(),
+            file.line_text(line),
+            " ".repeat(underline_start.get() - 1),
+            "^".repeat(underline_len),
+        )
+    }
+}
@@ -0,0 +1,101 @@
+use std::num::NonZeroUsize;
+
+use super::*;
+
+fn location(line: usize, col: usize) -> (Line, Column) {
+    (
+        Line::from(NonZeroUsize::new(line).unwrap()),
+        Column::from(NonZeroUsize::new(col).unwrap()),
+    )
+}
+
+#[test]
+fn offset_to_location() {
+    let mut source_map = SourceMap::new();
+    let file = source_map.add_snippet("let a = 1;\nlet é = a;\n".into());
+
+    assert_eq!(location(1, 1), source_map.location(file, 0));
+    assert_eq!(location(1, 5), source_map.location(file, 4));
+    assert_eq!(location(2, 1), source_map.location(file, 11));
+    // `é` is two bytes long but only one column wide
+    assert_eq!(location(2, 7), source_map.location(file, 18));
+    assert_eq!(location(3, 1), source_map.location(file, 23));
+}
+
+#[test]
+fn line_text_and_snippet() {
+    let mut source_map = SourceMap::new();
+    let first = source_map.add_snippet("first".into());
+    let second = source_map.add_snippet("let a = 1;\nlet b = a;".into());
+
+    assert_ne!(first, second);
+    assert_eq!(2, source_map.get(second).line_count());
+    assert_eq!(
+        "let b = a;",
+        source_map.get(second).line_text(location(2, 1).0)
+    );
+    assert_eq!("b = a", source_map.snippet(second, Span::new(15, 20)));
+    assert_eq!("first", source_map.text(first));
+}
+
+#[test]
+fn render_underlines_the_span() {
+    let mut source_map = SourceMap::new();
+    source_map.add_snippet("unused".into());
+    let file = source_map.add_snippet("let a = 1;\nlet b = a + c;".into());
+    let context = BriseContext::new(file, Span::new(19, 24), location(2, 9).0, location(2, 9).1);
+
+    let rendered = source_map.render(&context, "oops");
+
+    assert_eq!(
+        "<snippet>:2:9: oops\n  |\n2 | let b = a + c;\n  |         ^^^^^",
+        rendered
+    );
+}
+
+#[test]
+fn edit_keeps_lines_up_to_date() {
+    let edits = [
+        (4..4, "x\ny"),
+        (0..11, ""),
+        (10..17, "\n\n"),
+        (22..22, "\nlast"),
+    ];
+
+    for (span, replacement) in edits {
+        let mut source_map = SourceMap::new();
+        let file = source_map.add_snippet("let a = 1;\nlet é = a;\n".into());
+        source_map.edit(file, &TextEdit::new(span.clone(), replacement));
+
+        let mut expected = "let a = 1;\nlet é = a;\n".to_string();
+        expected.replace_range(span, replacement);
+        let fresh = SourceFile::new(SourceName::Snippet, expected.clone());
+
+        assert_eq!(expected, source_map.text(file));
+        assert_eq!(&fresh.line_index, source_map.get(file).line_index());
+    }
+}
+
+#[test]
+fn render_aligns_wide_characters() {
+    let mut source_map = SourceMap::new();
+    let file = source_map.add_snippet("let 字 = @;".into());
+    let context = BriseContext::new(file, 10..11, location(1, 9).0, location(1, 9).1);
+
+    assert_eq!(
+        "<snippet>:1:9: Unexpected\n  |\n1 | let 字 = @;\n  |          ^",
+        source_map.render(&context, "Unexpected")
+    );
+}
+
+#[test]
+fn render_span_on_a_crlf_line_ending() {
+    let mut source_map = SourceMap::new();
+    let file = source_map.add_snippet("ab\r\ncd".into());
+    let context = BriseContext::new(file, 3..4, location(1, 3).0, location(1, 3).1);
+
+    assert_eq!(
+        "<snippet>:1:3: Unexpected\n  |\n1 | ab\n  |   ^",
+        source_map.render(&context, "Unexpected")
+    );
+}
@@ -0,0 +1,113 @@
+use std::{
+    collections::HashMap,
+    fmt::Debug,
+    sync::{LazyLock, Mutex, OnceLock},
+};
+
+#[cfg(test)]
+mod tests;
+
+static INTERNER: LazyLock<Mutex<Interner>> = LazyLock::new(Mutex::default);
+
+/// Texts of the symbols, read without locking the interner
+static TEXTS: Arena = Arena::new();
+
+/// Interned string, two symbols are equal if and only if their texts are equal,
+/// so comparing or hashing a symbol never reads its text
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+pub struct Symbol(u32);
+
+impl Debug for Symbol {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Symbol")
+            .field(&self.0)
+            .field(&self.as_str())
+            .finish()
+    }
+}
+
+impl Symbol {
+    /// Symbol of the text, shared by every part of the program interning the same text
+    pub fn intern(text: &str) -> Self {
+        INTERNER
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .intern(text)
+    }
+
+    /// Doesn't lock the interner
+    pub fn as_str(&self) -> &'static str {
+        TEXTS.get(self.0)
+    }
+
+    pub fn index(&self) -> usize {
+        self.0 as usize
+    }
+}
+
+/// Every interned text is stored once and lives until the end of the program
+#[derive(Debug, Default)]
+struct Interner {
+    symbols: HashMap<&'static str, Symbol>,
+}
+
+impl Interner {
+    fn intern(&mut self, text: &str) -> Symbol {
+        if let Some(symbol) = self.symbols.get(text) {
+            return *symbol;
+        }
+
+        let index = self
+            .symbols
+            .len()
+            .try_into()
+            .expect("too many interned strings");
+        let text: &'static str = Box::leak(text.into());
+        TEXTS.push(index, text);
+        self.symbols.insert(text, Symbol(index));
+
+        Symbol(index)
+    }
+}
+
+/// Append-only list of texts, chunk `n` holds `2^n` texts and is allocated when its
+/// first text is pushed, so pushing never moves the texts already read
+struct Arena {
+    chunks: [OnceLock<Box<[OnceLock<&'static str>]>>; 33],
+}
+
+impl Arena {
+    const fn new() -> Self {
+        Self {
+            chunks: [const { OnceLock::new() }; 33],
+        }
+    }
+
+    /// Chunk and position in the chunk of the text of the symbol `index`
+    fn locate(index: u32) -> (usize, usize) {
+        let position = u64::from(index) + 1;
+        let chunk = position.ilog2();
+
+        (chunk as usize, (position - (1 << chunk)) as usize)
+    }
+
+    /// Only called by the interner, with the indexes in order
+    fn push(&self, index: u32, text: &'static str) {
+        let (chunk, offset) = Self::locate(index);
+        let slots =
+            self.chunks[chunk].get_or_init(|| (0..1 << chunk).map(|_| OnceLock::new()).collect());
+
+        slots[offset]
+            .set(text)
+            .expect("a symbol is only pushed once");
+    }
+
+    fn get(&self, index: u32) -> &'static str {
+        let (chunk, offset) = Self::locate(index);
+
+        self.chunks[chunk]
+            .get()
+            .and_then(|slots| slots[offset].get())
+            .expect("symbols are only made by the interner")
+    }
+}
@@ -0,0 +1,50 @@
+use std::thread;
+
+use super::*;
+
+#[test]
+fn same_text_same_symbol() {
+    let first = Symbol::intern("symbol_test_name");
+    let second = Symbol::intern(&String::from("symbol_test_name"));
+
+    assert_eq!(first, second);
+    assert_ne!(first, Symbol::intern("symbol_test_other"));
+    assert_eq!("symbol_test_name", first.as_str());
+}
+
+#[test]
+fn symbols_are_shared_between_threads() {
+    let symbols: Vec<Symbol> = (0..4)
+        .map(|_| thread::spawn(|| Symbol::intern("symbol_test_thread")))
+        .collect::<Vec<_>>()
+        .into_iter()
+        .map(|handle| handle.join().unwrap())
+        .collect();
+
+    assert!(symbols.iter().all(|symbol| *symbol == symbols[0]));
+    assert_eq!("symbol_test_thread", symbols[0].as_str());
+}
+
+#[test]
+fn texts_are_kept_across_chunks() {
+    let symbols: Vec<(String, Symbol)> = (0..1000)
+        .map(|index| format!("symbol_test_chunk_{index}"))
+        .map(|text| {
+            let symbol = Symbol::intern(&text);
+            (text, symbol)
+        })
+        .collect();
+
+    for (text, symbol) in &symbols {
+        assert_eq!(text, symbol.as_str());
+    }
+}
+
+#[test]
+fn chunks_hold_twice_the_previous_one() {
+    assert_eq!((0, 0), Arena::locate(0));
+    assert_eq!((1, 0), Arena::locate(1));
+    assert_eq!((1, 1), Arena::locate(2));
+    assert_eq!((2, 0), Arena::locate(3));
+    assert_eq!((32, 0), Arena::locate(u32::MAX));
+}
@@ -0,0 +1,40 @@
+use crate::Span;
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum TriviaKind {
+    /// Spaces, tabs and carriage returns not followed by `\n`
+    Whitespace,
+    /// `\n` or `\r\n`
+    Newline,
+    /// `// comment`
+    LineComment,
+    /// `/* comment */`
+    BlockComment,
+    /// `#!/usr/bin/env brise`, only on the first line
+    Shebang,
+    /// `U+FEFF` starting the input
+    ByteOrderMark,
+}
+
+/// Source text between two tokens, only kept by the lossless lexing mode
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub struct Trivia {
+    kind: TriviaKind,
+    span: Span,
+}
+
+impl Trivia {
+    pub fn new(kind: TriviaKind, span: Span) -> Self {
+        Self { kind, span }
+    }
+
+    pub fn kind(&self) -> TriviaKind {
+        self.kind
+    }
+
+    pub fn span(&self) -> Span {
+        self.span
+    }
+}